@@ -11,7 +11,24 @@
 use std::env;
 use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+#[path = "build/assets.rs"]
+mod assets;
+#[path = "build/bdf.rs"]
+mod bdf;
+#[path = "build/images.rs"]
+mod images;
+#[path = "build/midi.rs"]
+mod midi;
+#[path = "build/ringtones.rs"]
+mod ringtones;
+// Shared with the firmware, which uses more of it than the build does
+#[allow(dead_code)]
+#[path = "src/piicodev_buzzer/rtttl.rs"]
+mod rtttl;
+#[path = "build/songs.rs"]
+mod songs;
 
 fn main() {
     // Put `memory.x` in our output directory and ensure it's
@@ -56,6 +73,31 @@ fn main() {
     // `memory.x` is changed.
     println!("cargo:rerun-if-changed=memory.x");
 
+    // Convert images and fonts into constants for the display
+    let source = assets::generate(Path::new("assets")).unwrap_or_else(|error| panic!("{}", error));
+    File::create(out.join("assets.rs"))
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+
+    // Convert ringtones into constants for the buzzer
+    let source =
+        ringtones::generate(Path::new("assets")).unwrap_or_else(|error| panic!("{}", error));
+    File::create(out.join("ringtones.rs"))
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+
+    // Convert MIDI files into songs for the buzzer
+    let source = songs::generate(Path::new("assets")).unwrap_or_else(|error| panic!("{}", error));
+    File::create(out.join("songs.rs"))
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+    println!("cargo:rerun-if-changed=assets");
+    println!("cargo:rerun-if-changed=src/piicodev_buzzer/rtttl.rs");
+    println!("cargo:rerun-if-changed=build");
+
     // Build C library
     // Command::new("./c_build.sh").output().unwrap();
 
@@ -0,0 +1,113 @@
+//! Turns the images and fonts in `assets` into Rust constants for the SSD1306
+//!
+//! Each file becomes a constant named after it, so `assets/images/splash.pbm` becomes `SPLASH`.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use super::{bdf, images};
+
+/// Upper case identifier from a file name, with anything else replaced by underscores
+pub fn constant_name(path: &Path) -> String {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+
+    let mut name: String = stem
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() {
+                character.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if name.starts_with(|character: char| character.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+
+    name
+}
+
+/// Files directly inside `directory`, in a stable order, or none if it doesn't exist
+pub fn files(directory: &Path) -> Vec<std::path::PathBuf> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+
+    paths.sort();
+    paths
+}
+
+fn bitmap(image: &images::Image) -> String {
+    let bytes: Vec<String> = image
+        .to_pages()
+        .iter()
+        .map(|byte| format!("0x{:02X}", byte))
+        .collect();
+
+    format!(
+        "Bitmap::new({}, {}, &[{}])",
+        image.width,
+        image.height,
+        bytes.join(", ")
+    )
+}
+
+/// Generates the source of the assets module
+pub fn generate(assets: &Path) -> Result<String, String> {
+    let mut source = String::from("// Generated by build.rs from the assets folder\n\n");
+
+    for path in files(&assets.join("images")) {
+        let image = images::load(&path)?;
+
+        if image.width > u8::MAX as u32 || image.height > u8::MAX as u32 {
+            return Err(format!("{} is too big for the display", path.display()));
+        }
+
+        writeln!(
+            source,
+            "pub const {}: Bitmap = {};\n",
+            constant_name(&path),
+            bitmap(&image)
+        )
+        .unwrap();
+    }
+
+    for path in files(&assets.join("fonts")) {
+        let text = fs::read_to_string(&path)
+            .map_err(|error| format!("Couldn't read {}: {}", path.display(), error))?;
+        let font = bdf::parse(&text).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+        writeln!(
+            source,
+            "pub const {}: BitmapFont = BitmapFont {{\n    height: {},\n    glyphs: &[",
+            constant_name(&path),
+            font.height
+        )
+        .unwrap();
+
+        for glyph in &font.glyphs {
+            writeln!(
+                source,
+                "        Glyph {{ character: {:?}, x_offset: {}, y_offset: {}, advance: {}, bitmap: {} }},",
+                glyph.character,
+                glyph.x_offset,
+                glyph.y_offset,
+                glyph.advance,
+                bitmap(&glyph.image)
+            )
+            .unwrap();
+        }
+
+        writeln!(source, "    ],\n}};\n").unwrap();
+    }
+
+    Ok(source)
+}
@@ -0,0 +1,141 @@
+//! Parses bitmap fonts in Adobe's Glyph Bitmap Distribution Format (BDF)
+
+use super::images::Image;
+
+pub struct Glyph {
+    pub character: char,
+    /// Offset of the glyph's top left corner from where the character starts, with y measured
+    /// down from the top of the line
+    pub x_offset: i8,
+    pub y_offset: i8,
+    /// How far along the next character starts
+    pub advance: u8,
+    pub image: Image,
+}
+
+pub struct Font {
+    /// Distance from the top of the line to the bottom of the lowest descender
+    pub height: u8,
+    /// Sorted by character
+    pub glyphs: Vec<Glyph>,
+}
+
+fn numbers(values: &str) -> Result<Vec<i32>, String> {
+    values
+        .split_whitespace()
+        .map(|value| value.parse().map_err(|_| format!("Bad number: {}", value)))
+        .collect()
+}
+
+/// Glyphs with no Unicode encoding (`ENCODING -1`) are skipped
+pub fn parse(text: &str) -> Result<Font, String> {
+    let mut lines = text.lines().map(str::trim);
+
+    let mut ascent = None;
+    let mut descent = None;
+    let mut bounding_box = None;
+    let mut glyphs = Vec::new();
+
+    while let Some(line) = lines.next() {
+        let (keyword, values) = line.split_once(' ').unwrap_or((line, ""));
+
+        match keyword {
+            "FONTBOUNDINGBOX" => bounding_box = Some(numbers(values)?),
+            "FONT_ASCENT" => ascent = numbers(values)?.first().copied(),
+            "FONT_DESCENT" => descent = numbers(values)?.first().copied(),
+            "STARTCHAR" => {
+                let mut encoding = None;
+                let mut advance = 0;
+                let mut bbx = vec![0; 4];
+
+                // Everything up to the bitmap describes the glyph
+                for line in lines.by_ref() {
+                    let (keyword, values) = line.split_once(' ').unwrap_or((line, ""));
+
+                    match keyword {
+                        "ENCODING" => encoding = numbers(values)?.first().copied(),
+                        "DWIDTH" => advance = numbers(values)?.first().copied().unwrap_or(0),
+                        "BBX" => bbx = numbers(values)?,
+                        "BITMAP" => break,
+                        _ => {}
+                    }
+                }
+
+                let [width, height, x_offset, y_offset] = bbx[..] else {
+                    return Err(format!("Bad BBX for {}", values));
+                };
+
+                // Bitmaps on the display are at most 255 pixels each way
+                if !(0..=u8::MAX as i32).contains(&width) || !(0..=u8::MAX as i32).contains(&height)
+                {
+                    return Err(format!(
+                        "Bad BBX for {}: {}x{} isn't between 0x0 and 255x255",
+                        values, width, height
+                    ));
+                }
+
+                let mut image = Image {
+                    width: width as u32,
+                    height: height as u32,
+                    pixels: vec![false; (width * height) as usize],
+                };
+
+                for y in 0..height {
+                    let row = lines.next().ok_or("Font ends in the middle of a glyph")?;
+                    let bad_row = || format!("Bad bitmap row for {}: {}", values, row);
+
+                    // Each hex digit is 4 pixels, with the leftmost in the highest bit
+                    for x in 0..width {
+                        let digit = row.chars().nth(x as usize / 4).ok_or_else(bad_row)?;
+                        let digit = digit.to_digit(16).ok_or_else(bad_row)?;
+
+                        image.pixels[(y * width + x) as usize] = digit & (0x8 >> (x % 4)) != 0;
+                    }
+                }
+
+                let character = encoding
+                    .filter(|encoding| *encoding >= 0)
+                    .and_then(|encoding| char::from_u32(encoding as u32));
+
+                if let Some(character) = character {
+                    glyphs.push((values, character, advance, x_offset, y_offset, image));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Fall back to the bounding box for fonts without the usual properties
+    let bounding_box = bounding_box.ok_or("Font has no FONTBOUNDINGBOX")?;
+    let descent = descent.unwrap_or(-bounding_box[3]);
+    let ascent = ascent.unwrap_or(bounding_box[1] - descent);
+
+    let mut glyphs: Vec<Glyph> = glyphs
+        .into_iter()
+        .map(|(name, character, advance, x_offset, y_offset, image)| {
+            // BDF measures up from the baseline to the bottom of the glyph
+            let y_offset = ascent - y_offset - image.height as i32;
+
+            Ok(Glyph {
+                character,
+                x_offset: i8::try_from(x_offset)
+                    .map_err(|_| format!("X offset for {} is out of range: {}", name, x_offset))?,
+                y_offset: i8::try_from(y_offset)
+                    .map_err(|_| format!("Y offset for {} is out of range: {}", name, y_offset))?,
+                advance: u8::try_from(advance)
+                    .map_err(|_| format!("DWIDTH for {} is out of range: {}", name, advance))?,
+                image,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+
+    glyphs.sort_by_key(|glyph| glyph.character);
+
+    let height = ascent + descent;
+
+    Ok(Font {
+        height: u8::try_from(height)
+            .map_err(|_| format!("Font height is out of range: {}", height))?,
+        glyphs,
+    })
+}
@@ -0,0 +1,263 @@
+//! Loads 1-bit images for the SSD1306 from PBM, XBM and PNG files
+//!
+//! Set pixels (black ink in PBM and XBM, bright or opaque pixels in PNG) are the ones lit up on
+//! the display.
+
+use std::fs::{self, File};
+use std::path::Path;
+
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    /// Row by row, top to bottom
+    pub pixels: Vec<bool>,
+}
+
+impl Image {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![false; (width * height) as usize],
+        }
+    }
+
+    fn set(&mut self, x: u32, y: u32, on: bool) {
+        self.pixels[(y * self.width + x) as usize] = on;
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    ///
+    /// Lays the image out like the SSD1306's display RAM
+    ///
+    /// Each byte is a column of 8 pixels with the top one in the least significant bit, running
+    /// left to right along each 8 pixel tall page.
+    ///
+    pub fn to_pages(&self) -> Vec<u8> {
+        let pages = self.height.div_ceil(8);
+        let mut bytes = vec![0; (self.width * pages) as usize];
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.get(x, y) {
+                    bytes[(x + (y / 8) * self.width) as usize] |= 1 << (y % 8);
+                }
+            }
+        }
+
+        bytes
+    }
+}
+
+pub fn load(path: &Path) -> Result<Image, String> {
+    let extension = path.extension().and_then(|extension| extension.to_str());
+
+    match extension
+        .map(|extension| extension.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("pbm") => parse_pbm(&read(path)?),
+        Some("xbm") => parse_xbm(&String::from_utf8_lossy(&read(path)?)),
+        Some("png") => load_png(path),
+        _ => Err(format!("{} isn't a PBM, XBM or PNG image", path.display())),
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|error| format!("Couldn't read {}: {}", path.display(), error))
+}
+
+/// Splits the text part of a PBM header into tokens, skipping `#` comments
+struct PbmHeader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> PbmHeader<'a> {
+    fn token(&mut self) -> Option<&'a [u8]> {
+        loop {
+            match self.bytes.get(self.position)? {
+                b'#' => {
+                    while self
+                        .bytes
+                        .get(self.position)
+                        .is_some_and(|byte| *byte != b'\n')
+                    {
+                        self.position += 1;
+                    }
+                }
+                byte if byte.is_ascii_whitespace() => self.position += 1,
+                _ => break,
+            }
+        }
+
+        let start = self.position;
+
+        while self
+            .bytes
+            .get(self.position)
+            .is_some_and(|byte| !byte.is_ascii_whitespace() && *byte != b'#')
+        {
+            self.position += 1;
+        }
+
+        Some(&self.bytes[start..self.position])
+    }
+
+    fn number(&mut self) -> Result<u32, String> {
+        let token = self.token().ok_or("PBM header is cut short")?;
+
+        std::str::from_utf8(token)
+            .ok()
+            .and_then(|token| token.parse().ok())
+            .ok_or_else(|| format!("Bad number in PBM header: {:?}", token))
+    }
+}
+
+/// Plain (P1) and raw (P4) portable bitmaps
+fn parse_pbm(bytes: &[u8]) -> Result<Image, String> {
+    let mut header = PbmHeader { bytes, position: 0 };
+
+    let magic = header.token().ok_or("Empty PBM file")?;
+    let width = header.number()?;
+    let height = header.number()?;
+    let mut image = Image::new(width, height);
+
+    match magic {
+        b"P1" => {
+            // Pixels are single 0 or 1 characters, which don't need separating
+            let mut pixels = bytes[header.position..]
+                .split(|byte| *byte == b'\n')
+                .flat_map(|line| line.split(|byte| *byte == b'#').next().unwrap_or(&[]))
+                .filter(|byte| **byte == b'0' || **byte == b'1');
+
+            for y in 0..height {
+                for x in 0..width {
+                    let pixel = pixels.next().ok_or("PBM image data is cut short")?;
+                    image.set(x, y, *pixel == b'1');
+                }
+            }
+        }
+        b"P4" => {
+            // A single whitespace character separates the header from the data
+            let data = bytes
+                .get(header.position + 1..)
+                .ok_or("PBM image data is cut short")?;
+            let row_bytes = width.div_ceil(8);
+
+            for y in 0..height {
+                for x in 0..width {
+                    let byte = data
+                        .get((y * row_bytes + x / 8) as usize)
+                        .ok_or("PBM image data is cut short")?;
+
+                    image.set(x, y, byte & (0x80 >> (x % 8)) != 0);
+                }
+            }
+        }
+        _ => return Err("Only P1 and P4 PBM images are supported".into()),
+    }
+
+    Ok(image)
+}
+
+/// X BitMaps, as written by GIMP and the `bitmap` tool
+fn parse_xbm(text: &str) -> Result<Image, String> {
+    let define = |suffix: &str| {
+        text.lines()
+            .filter_map(|line| line.trim().strip_prefix("#define"))
+            .find_map(|line| {
+                let mut parts = line.split_whitespace();
+                let name = parts.next()?;
+
+                if !name.ends_with(suffix) {
+                    return None;
+                }
+
+                parts.next()?.parse::<u32>().ok()
+            })
+            .ok_or_else(|| format!("XBM file has no {} define", suffix))
+    };
+
+    let width = define("_width")?;
+    let height = define("_height")?;
+
+    let start = text.find('{').ok_or("XBM file has no data")?;
+    let end = text[start..].find('}').ok_or("XBM data isn't closed")? + start;
+
+    let data = text[start + 1..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            let digits = value.trim_start_matches("0x").trim_start_matches("0X");
+            u8::from_str_radix(digits, 16).map_err(|_| format!("Bad XBM byte: {}", value))
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+
+    let row_bytes = width.div_ceil(8);
+    let mut image = Image::new(width, height);
+
+    for y in 0..height {
+        for x in 0..width {
+            let byte = data
+                .get((y * row_bytes + x / 8) as usize)
+                .ok_or("XBM image data is cut short")?;
+
+            // Unlike PBM, the leftmost pixel is the least significant bit
+            image.set(x, y, byte & (1 << (x % 8)) != 0);
+        }
+    }
+
+    Ok(image)
+}
+
+/// Any PNG, thresholded to 1 bit. Transparent pixels are left off.
+fn load_png(path: &Path) -> Result<Image, String> {
+    let file = File::open(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    let error = |error: png::DecodingError| format!("{}: {}", path.display(), error);
+
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+
+    let mut reader = decoder.read_info().map_err(error)?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(error)?;
+
+    let channels = match info.color_type {
+        png::ColorType::Grayscale => 1,
+        png::ColorType::GrayscaleAlpha => 2,
+        png::ColorType::Rgb => 3,
+        png::ColorType::Rgba => 4,
+        png::ColorType::Indexed => return Err("Palette wasn't expanded".into()),
+    };
+
+    let mut image = Image::new(info.width, info.height);
+
+    for y in 0..info.height {
+        let row = &buffer[(y as usize * info.line_size)..];
+
+        for x in 0..info.width {
+            let pixel = &row[x as usize * channels..(x as usize + 1) * channels];
+
+            let (luma, alpha) = match channels {
+                1 => (pixel[0] as u32, 255),
+                2 => (pixel[0] as u32, pixel[1]),
+                3 => (luma(pixel), 255),
+                _ => (luma(pixel), pixel[3]),
+            };
+
+            image.set(x, y, luma >= 128 && alpha >= 128);
+        }
+    }
+
+    Ok(image)
+}
+
+/// Perceived brightness of an RGB pixel, from 0 to 255
+fn luma(pixel: &[u8]) -> u32 {
+    (pixel[0] as u32 * 299 + pixel[1] as u32 * 587 + pixel[2] as u32 * 114) / 1000
+}
@@ -0,0 +1,294 @@
+//! Reads the notes out of Standard MIDI Files (format 0 and 1)
+//!
+//! Only what's needed for the buzzer is kept: when each note starts and stops, and the tempo.
+
+/// 120 beats per minute, which MIDI files use until they say otherwise
+const DEFAULT_TEMPO: u32 = 500_000;
+
+/// Drums are on channel 10 by convention, counting from 1, and don't make sense as a melody
+pub const DRUM_CHANNEL: u8 = 9;
+
+pub struct NoteEvent {
+    /// Ticks since the start of the track
+    pub tick: u64,
+    /// From 0, so one less than most editors show
+    pub channel: u8,
+    pub key: u8,
+    /// Whether the note starts or stops
+    pub on: bool,
+}
+
+pub struct MidiFile {
+    pub ticks_per_quarter: u16,
+    /// The first tempo in the file, as changes part way through aren't supported
+    pub microseconds_per_quarter: u32,
+    pub tracks: Vec<Vec<NoteEvent>>,
+}
+
+/// Reads through the bytes of a file, failing if they run out
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], String> {
+        let end = self.position + count;
+        let bytes = self
+            .bytes
+            .get(self.position..end)
+            .ok_or("File ends unexpectedly")?;
+
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// A variable length quantity, 7 bits per byte with the top bit set on all but the last
+    fn variable(&mut self) -> Result<u32, String> {
+        let mut value = 0;
+
+        for _ in 0..4 {
+            let byte = self.byte()?;
+            value = (value << 7) | (byte & 0x7F) as u32;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err("Variable length quantity is too long".into())
+    }
+
+    /// The next chunk's type and contents
+    fn chunk(&mut self) -> Result<(&'a [u8], Reader<'a>), String> {
+        let kind = self.take(4)?;
+        let length = self.u32()? as usize;
+        let bytes = self.take(length)?;
+
+        Ok((kind, Reader { bytes, position: 0 }))
+    }
+
+    fn is_done(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+}
+
+/// Reads a track's notes, and its first tempo if it has one
+fn parse_track(mut track: Reader) -> Result<(Vec<NoteEvent>, Option<u32>), String> {
+    let mut events = Vec::new();
+    let mut tempo = None;
+    let mut tick: u64 = 0;
+    let mut running_status = None;
+
+    while !track.is_done() {
+        tick += track.variable()? as u64;
+
+        let mut status = track.byte()?;
+
+        // Running status leaves out the status byte when it's the same as the last one
+        let first_data = if status < 0x80 {
+            let data = status;
+            status = running_status.ok_or("Data byte without a status")?;
+            Some(data)
+        } else {
+            None
+        };
+
+        match status {
+            0xFF => {
+                let kind = track.byte()?;
+                let length = track.variable()? as usize;
+                let data = track.take(length)?;
+
+                match kind {
+                    0x51 if data.len() == 3 && tempo.is_none() => {
+                        tempo = Some(u32::from_be_bytes([0, data[0], data[1], data[2]]));
+                    }
+                    0x2F => break,
+                    _ => {}
+                }
+            }
+            0xF0 | 0xF7 => {
+                let length = track.variable()? as usize;
+                track.take(length)?;
+            }
+            0x80..=0xEF => {
+                running_status = Some(status);
+
+                let first = match first_data {
+                    Some(data) => data,
+                    None => track.byte()?,
+                };
+
+                // Program change and channel pressure are the only ones with a single data byte
+                let second = match status & 0xF0 {
+                    0xC0 | 0xD0 => 0,
+                    _ => track.byte()?,
+                };
+
+                let channel = status & 0x0F;
+
+                match status & 0xF0 {
+                    // A note on with no velocity is the same as a note off
+                    0x90 if second > 0 => events.push(NoteEvent {
+                        tick,
+                        channel,
+                        key: first,
+                        on: true,
+                    }),
+                    0x80 | 0x90 => events.push(NoteEvent {
+                        tick,
+                        channel,
+                        key: first,
+                        on: false,
+                    }),
+                    _ => {}
+                }
+            }
+            _ => return Err(format!("Unknown status byte 0x{:02X}", status)),
+        }
+    }
+
+    Ok((events, tempo))
+}
+
+pub fn parse(bytes: &[u8]) -> Result<MidiFile, String> {
+    let mut file = Reader { bytes, position: 0 };
+
+    let (kind, mut header) = file.chunk()?;
+
+    if kind != b"MThd" {
+        return Err("Not a MIDI file".into());
+    }
+
+    let format = header.u16()?;
+    let _track_count = header.u16()?;
+    let division = header.u16()?;
+
+    if format > 1 {
+        return Err(format!("Format {} MIDI files aren't supported", format));
+    }
+
+    if division & 0x8000 != 0 || division == 0 {
+        return Err("MIDI files timed in SMPTE frames aren't supported".into());
+    }
+
+    let mut tracks = Vec::new();
+    let mut tempo = None;
+
+    while !file.is_done() {
+        let (kind, track) = file.chunk()?;
+
+        // Anything else is an extension that readers are meant to skip
+        if kind != b"MTrk" {
+            continue;
+        }
+
+        let (events, track_tempo) = parse_track(track)?;
+
+        tracks.push(events);
+        tempo = tempo.or(track_tempo);
+    }
+
+    Ok(MidiFile {
+        ticks_per_quarter: division,
+        microseconds_per_quarter: tempo.unwrap_or(DEFAULT_TEMPO),
+        tracks,
+    })
+}
+
+impl MidiFile {
+    /// The tempo in quarter notes per minute
+    pub fn bpm(&self) -> u16 {
+        let bpm = (60_000_000.0 / self.microseconds_per_quarter.max(1) as f64).round();
+
+        bpm.clamp(1.0, u16::MAX as f64) as u16
+    }
+
+    ///
+    /// Reduces the notes to a single line the buzzer can play, as pitches and how many ticks each
+    /// lasts, with `None` for silence
+    ///
+    /// `track` and `channel` count from 0, and leaving either out takes notes from all of them,
+    /// apart from drums. Whenever notes overlap, the highest one is heard. Silence before the
+    /// first note and after the last is left out.
+    ///
+    pub fn melody(&self, track: Option<usize>, channel: Option<u8>) -> Vec<(Option<u8>, u64)> {
+        let mut events: Vec<&NoteEvent> = self
+            .tracks
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| track.is_none_or(|track| track == *index))
+            .flat_map(|(_, events)| events)
+            .filter(|event| match channel {
+                Some(channel) => event.channel == channel,
+                None => event.channel != DRUM_CHANNEL,
+            })
+            .collect();
+
+        // Notes that stop and start on the same tick are handled in that order
+        events.sort_by_key(|event| (event.tick, event.on));
+
+        // How many times each key is being held down, as tracks can overlap
+        let mut held = [0u32; 128];
+        let mut segments: Vec<(Option<u8>, u64)> = Vec::new();
+        let mut current: Option<u8> = None;
+        let mut current_start = None;
+
+        let mut index = 0;
+
+        while index < events.len() {
+            let tick = events[index].tick;
+            let mut struck = [false; 128];
+
+            while let Some(event) = events.get(index).filter(|event| event.tick == tick) {
+                let key = event.key as usize & 0x7F;
+
+                if event.on {
+                    held[key] += 1;
+                    struck[key] = true;
+                } else {
+                    held[key] = held[key].saturating_sub(1);
+                }
+
+                index += 1;
+            }
+
+            let highest = (0..128u8).rev().find(|key| held[*key as usize] > 0);
+
+            // A highest note played again starts over, rather than carrying on
+            let restruck = highest.is_some_and(|key| struck[key as usize]);
+
+            if highest == current && !restruck {
+                continue;
+            }
+
+            if let Some(start) = current_start {
+                segments.push((current, tick - start));
+            }
+
+            // Only start counting time once there's been a note
+            if highest.is_some() || current_start.is_some() {
+                current_start = Some(tick);
+            }
+
+            current = highest;
+        }
+
+        segments
+    }
+}
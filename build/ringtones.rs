@@ -0,0 +1,42 @@
+//! Turns the RTTTL ringtones in `assets/ringtones` into Rust constants for the buzzer
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use super::assets::{constant_name, files};
+use super::rtttl::Rtttl;
+
+/// Generates the source of the ringtones module
+pub fn generate(assets: &Path) -> Result<String, String> {
+    let mut source = String::from("// Generated by build.rs from the assets folder\n\n");
+
+    for path in files(&assets.join("ringtones")) {
+        let text = fs::read_to_string(&path)
+            .map_err(|error| format!("Couldn't read {}: {}", path.display(), error))?;
+        let ringtone =
+            Rtttl::parse(&text).map_err(|error| format!("{}: {:?}", path.display(), error))?;
+
+        writeln!(
+            source,
+            "pub const {}: Ringtone = Ringtone {{\n    name: {:?},\n    bpm: {},\n    notes: &[",
+            constant_name(&path),
+            ringtone.name,
+            ringtone.bpm
+        )
+        .unwrap();
+
+        for note in ringtone.notes() {
+            writeln!(
+                source,
+                "        RtttlNote::new({:?}, {}, {}),",
+                note.midi, note.duration, note.dotted
+            )
+            .unwrap();
+        }
+
+        writeln!(source, "    ],\n}};\n").unwrap();
+    }
+
+    Ok(source)
+}
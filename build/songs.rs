@@ -0,0 +1,161 @@
+//! Turns the MIDI files in `assets/songs` into `Song` constants for the buzzer
+//!
+//! Every note is snapped to lengths a `NoteLength` can describe, tied together when it takes more
+//! than one. A text file with the same name, such as `harmony.txt` next to `harmony.mid`, can pick
+//! out the melody with `track = 2` and `channel = 1` lines, counting from 1 the way most editors
+//! do.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use super::assets::{constant_name, files};
+use super::midi;
+
+/// Note lengths are worked out in steps this small, as it fits both 32nd notes and triplets
+const STEPS_PER_QUARTER: u64 = 24;
+
+/// Each note length as source code and in steps, longest first. Lengths are split into as few of
+/// these as possible, trying the straight ones before triplets.
+const LENGTHS: [(&str, u64); 17] = [
+    ("NoteLength::WHOLE.dotted()", 144),
+    ("NoteLength::WHOLE", 96),
+    ("NoteLength::HALF.dotted()", 72),
+    ("NoteLength::HALF", 48),
+    ("NoteLength::QUARTER.dotted()", 36),
+    ("NoteLength::QUARTER", 24),
+    ("NoteLength::EIGHTH.dotted()", 18),
+    ("NoteLength::EIGHTH", 12),
+    ("NoteLength::SIXTEENTH.dotted()", 9),
+    ("NoteLength::SIXTEENTH", 6),
+    ("NoteLength::THIRTY_SECOND", 3),
+    ("NoteLength::WHOLE.triplet()", 64),
+    ("NoteLength::HALF.triplet()", 32),
+    ("NoteLength::QUARTER.triplet()", 16),
+    ("NoteLength::EIGHTH.triplet()", 8),
+    ("NoteLength::SIXTEENTH.triplet()", 4),
+    ("NoteLength::THIRTY_SECOND.triplet()", 2),
+];
+
+/// Which track and channel to take the melody from, counting from 0
+#[derive(Default)]
+struct Selection {
+    track: Option<usize>,
+    channel: Option<u8>,
+}
+
+fn selection(path: &Path) -> Result<Selection, String> {
+    let mut selection = Selection::default();
+
+    let Ok(text) = fs::read_to_string(path) else {
+        return Ok(selection);
+    };
+
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let bad_line = || format!("{}: Bad line: {}", path.display(), line);
+
+        let (key, value) = line.split_once('=').ok_or_else(bad_line)?;
+        let value: u32 = value.trim().parse().map_err(|_| bad_line())?;
+
+        match (key.trim(), value) {
+            ("track", 1..) => selection.track = Some(value as usize - 1),
+            ("channel", 1..=16) => selection.channel = Some(value as u8 - 1),
+            _ => return Err(bad_line()),
+        }
+    }
+
+    Ok(selection)
+}
+
+/// Splits a number of steps into note lengths, along with how many are left over as they're too
+/// short to write
+fn lengths(steps: u64) -> (Vec<&'static str>, u64) {
+    if let Some((length, _)) = LENGTHS.iter().find(|(_, size)| *size == steps) {
+        return (vec![length], 0);
+    }
+
+    let mut remaining = steps;
+    let mut lengths = Vec::new();
+
+    for (length, size) in LENGTHS {
+        while remaining >= size {
+            lengths.push(length);
+            remaining -= size;
+        }
+    }
+
+    (lengths, remaining)
+}
+
+fn note(pitch: Option<u8>) -> String {
+    match pitch {
+        Some(key) => format!("Note::Midi({})", key),
+        None => "Note::Rest".into(),
+    }
+}
+
+/// Generates the source of the songs module
+pub fn generate(assets: &Path) -> Result<String, String> {
+    let mut source = String::from("// Generated by build.rs from the assets folder\n\n");
+
+    let songs = files(&assets.join("songs"));
+
+    for path in songs.iter().filter(|path| {
+        path.extension()
+            .is_some_and(|extension| extension == "mid" || extension == "midi")
+    }) {
+        let bytes = fs::read(path)
+            .map_err(|error| format!("Couldn't read {}: {}", path.display(), error))?;
+        let file = midi::parse(&bytes).map_err(|error| format!("{}: {}", path.display(), error))?;
+        let selection = selection(&path.with_extension("txt"))?;
+
+        let melody = file.melody(selection.track, selection.channel);
+
+        if melody.is_empty() {
+            return Err(format!("{} has no notes to play", path.display()));
+        }
+
+        writeln!(
+            source,
+            "pub const {}: Song = Song::new(\n    {},\n    &[",
+            constant_name(path),
+            file.bpm()
+        )
+        .unwrap();
+
+        // Snapping where each note starts and stops, rather than how long it is, keeps rounding
+        // from building up over the song
+        let to_steps = |ticks: u64| {
+            let ticks_per_quarter = file.ticks_per_quarter as u64;
+            (ticks * STEPS_PER_QUARTER + ticks_per_quarter / 2) / ticks_per_quarter
+        };
+
+        let mut start = 0;
+
+        // Steps too short to write are added on to the next note, so the ones after it stay in
+        // time. A note made up of nothing else is left out, as is a step left after the last note.
+        let mut carried = 0;
+
+        for (pitch, ticks) in melody {
+            let steps = to_steps(start + ticks) - to_steps(start) + carried;
+            start += ticks;
+
+            let (lengths, leftover) = lengths(steps);
+            carried = leftover;
+
+            // A note that takes more than one length is tied across them, so it's held throughout
+            for (index, length) in lengths.iter().enumerate() {
+                let tie = match pitch.is_some() && index + 1 < lengths.len() {
+                    true => ".tied()",
+                    false => "",
+                };
+
+                writeln!(source, "        ({}, {}{}),", note(pitch), length, tie).unwrap();
+            }
+        }
+
+        writeln!(source, "    ],\n);\n").unwrap();
+    }
+
+    Ok(source)
+}
@@ -0,0 +1,27 @@
+//! Converts the firmware's assets the same way its own build script does, for the display tests
+
+use std::env;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[path = "../build/assets.rs"]
+mod assets;
+#[path = "../build/bdf.rs"]
+mod bdf;
+#[path = "../build/images.rs"]
+mod images;
+
+fn main() {
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+
+    let source =
+        assets::generate(Path::new("../assets")).unwrap_or_else(|error| panic!("{}", error));
+    File::create(out.join("assets.rs"))
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+
+    println!("cargo:rerun-if-changed=../assets");
+    println!("cargo:rerun-if-changed=../build");
+}
@@ -0,0 +1,38 @@
+//! The firmware's hardware-independent modules, built for the host so they can be tested
+//!
+//! Modules are included from `src` by path, under the same names as in the firmware where their
+//! `crate::` and `super::` paths need to resolve. This crate is `no_std` like the firmware, so
+//! anything only available in `std` fails to build here too.
+
+#![no_std]
+
+#[path = "../../src/piicodev_rgb/animation.rs"]
+pub mod animation;
+#[path = "../../src/piicodev_qmc6310/calibration.rs"]
+pub mod calibration;
+#[path = "../../src/piicodev_rgb/colour.rs"]
+pub mod colour;
+#[path = "../../src/piicodev_qmc6310/declination.rs"]
+pub mod declination;
+#[path = "../../src/piicodev_rgb/effects.rs"]
+pub mod effects;
+#[path = "../../src/piicodev_qmc6310/filter.rs"]
+pub mod filter;
+#[path = "../../src/piicodev_rgb/mapping.rs"]
+pub mod mapping;
+#[path = "../../src/piicodev_buzzer/notes.rs"]
+pub mod notes;
+/// Stands in for the RGB driver, which needs the hardware, with the tuple that colours convert to
+pub mod piicodev_rgb {
+    pub type RGB = (u8, u8, u8);
+}
+#[path = "../../src/piicodev_ssd1306/mod.rs"]
+pub mod piicodev_ssd1306;
+#[path = "../../src/piicodev_buzzer/player.rs"]
+pub mod player;
+#[path = "../../src/ring_buffer.rs"]
+pub mod ring_buffer;
+#[path = "../../src/piicodev_buzzer/rtttl.rs"]
+pub mod rtttl;
+#[path = "../../src/screens.rs"]
+pub mod screens;
@@ -0,0 +1,216 @@
+use host_tests::animation::{Animator, Effect, PixelDisplay, MAX_PIXELS};
+use host_tests::colour::Colour;
+use host_tests::effects::{Blink, Chase, Wipe};
+
+#[derive(Debug, PartialEq)]
+struct ShowFailed;
+
+/// Remembers the colours it's given, and how often they're sent
+struct MockDisplay {
+    pixels: Vec<Colour>,
+    /// What the LEDs were last sent
+    shown: Vec<Colour>,
+    shows: usize,
+    /// How many times a pixel has been set
+    changes: usize,
+    fail_next_show: bool,
+}
+
+impl MockDisplay {
+    fn new(count: usize) -> Self {
+        Self {
+            pixels: vec![Colour::BLACK; count],
+            shown: vec![Colour::BLACK; count],
+            shows: 0,
+            changes: 0,
+            fail_next_show: false,
+        }
+    }
+}
+
+impl PixelDisplay for MockDisplay {
+    type Error = ShowFailed;
+
+    fn pixel_count(&self) -> usize {
+        self.pixels.len()
+    }
+
+    fn set_colour(&mut self, index: usize, colour: Colour) {
+        self.pixels[index] = colour;
+        self.changes += 1;
+    }
+
+    fn show(&mut self) -> Result<(), ShowFailed> {
+        if self.fail_next_show {
+            self.fail_next_show = false;
+            return Err(ShowFailed);
+        }
+
+        self.shown.clone_from(&self.pixels);
+        self.shows += 1;
+
+        Ok(())
+    }
+}
+
+/// The colours `effect` gives each of `count` pixels at `elapsed_ms`
+fn frame(effect: &mut impl Effect, elapsed_ms: u32, count: usize) -> Vec<Colour> {
+    effect.update(elapsed_ms);
+
+    (0..count)
+        .map(|index| effect.colour(elapsed_ms, index, count))
+        .collect()
+}
+
+/// Turns a frame into a picture, with `#` for `lit` and `.` for anything else
+fn lit(frame: &[Colour], colour: Colour) -> String {
+    frame
+        .iter()
+        .map(|pixel| if *pixel == colour { '#' } else { '.' })
+        .collect()
+}
+
+#[test]
+fn the_first_frame_is_always_shown() {
+    let mut animator = Animator::new(Blink::new(Colour::BLACK, Colour::BLACK, 100, 100));
+    let mut display = MockDisplay::new(3);
+
+    assert_eq!(animator.tick(0, &mut display), Ok(true));
+    assert_eq!(display.shows, 1);
+    assert_eq!(display.shown, [Colour::BLACK; 3]);
+}
+
+#[test]
+fn only_shows_when_a_pixel_changes() {
+    let mut animator = Animator::new(Blink::new(Colour::RED, Colour::BLACK, 100, 100));
+    let mut display = MockDisplay::new(3);
+
+    let shown: Vec<bool> = [1000, 1050, 1099, 1100, 1150, 1200]
+        .into_iter()
+        .map(|now_ms| animator.tick(now_ms, &mut display).unwrap())
+        .collect();
+
+    assert_eq!(shown, [true, false, false, true, false, true]);
+    assert_eq!(display.shows, 3);
+    assert_eq!(display.shown, [Colour::RED; 3]);
+}
+
+#[test]
+fn only_changed_pixels_are_set() {
+    let mut animator = Animator::new(Chase::new(Colour::RED, Colour::BLACK, 100, 4));
+    let mut display = MockDisplay::new(8);
+
+    animator.tick(0, &mut display).unwrap();
+    display.changes = 0;
+
+    // Pixels 0 and 4 go out, and 1 and 5 come on
+    animator.tick(100, &mut display).unwrap();
+
+    assert_eq!(display.shows, 2);
+    assert_eq!(display.changes, 4);
+    assert_eq!(lit(&display.shown, Colour::RED), ".#...#..");
+
+    animator.tick(150, &mut display).unwrap();
+
+    assert_eq!(display.shows, 2);
+    assert_eq!(display.changes, 4);
+}
+
+#[test]
+fn a_failed_show_is_retried_in_full() {
+    let mut animator = Animator::new(Blink::new(Colour::RED, Colour::BLACK, 100, 100));
+    let mut display = MockDisplay::new(3);
+
+    display.fail_next_show = true;
+    assert_eq!(animator.tick(0, &mut display), Err(ShowFailed));
+
+    // Nothing has changed since, but the LEDs never got it
+    assert_eq!(animator.tick(10, &mut display), Ok(true));
+    assert_eq!(display.shown, [Colour::RED; 3]);
+    assert_eq!(animator.tick(20, &mut display), Ok(false));
+}
+
+#[test]
+fn restarting_shows_the_first_frame_again() {
+    let mut animator = Animator::new(Blink::new(Colour::RED, Colour::BLACK, 100, 100));
+    let mut display = MockDisplay::new(3);
+
+    animator.tick(0, &mut display).unwrap();
+    animator.tick(150, &mut display).unwrap();
+    assert_eq!(display.shown, [Colour::BLACK; 3]);
+
+    animator.restart();
+
+    assert_eq!(animator.tick(5000, &mut display), Ok(true));
+    assert_eq!(display.shown, [Colour::RED; 3]);
+}
+
+#[test]
+fn time_wrapping_around_is_fine() {
+    let mut animator = Animator::new(Blink::new(Colour::RED, Colour::BLACK, 100, 100));
+    let mut display = MockDisplay::new(1);
+
+    animator.tick(u32::MAX - 49, &mut display).unwrap();
+
+    assert_eq!(animator.tick(49, &mut display), Ok(false));
+    assert_eq!(animator.tick(50, &mut display), Ok(true));
+    assert_eq!(display.shown, [Colour::BLACK]);
+}
+
+#[test]
+fn displays_longer_than_the_frame_are_cut_short() {
+    let mut animator = Animator::new(Blink::new(Colour::RED, Colour::BLACK, 100, 100));
+    let mut display = MockDisplay::new(MAX_PIXELS + 2);
+
+    animator.tick(0, &mut display).unwrap();
+
+    assert_eq!(display.pixels[MAX_PIXELS - 1], Colour::RED);
+    assert_eq!(display.pixels[MAX_PIXELS], Colour::BLACK);
+}
+
+#[test]
+fn wipe_fills_then_clears_one_pixel_a_step() {
+    let mut wipe = Wipe::new(Colour::RED, Colour::BLACK, 100);
+
+    let steps: Vec<String> = (0..9)
+        .map(|step| lit(&frame(&mut wipe, step * 100 + 50, 4), Colour::RED))
+        .collect();
+
+    assert_eq!(
+        steps,
+        ["#...", "##..", "###.", "####", ".###", "..##", "...#", "....", "#..."]
+    );
+}
+
+#[test]
+fn wipe_changes_at_the_end_of_each_step() {
+    let mut wipe = Wipe::new(Colour::RED, Colour::BLACK, 100);
+
+    assert_eq!(lit(&frame(&mut wipe, 99, 4), Colour::RED), "#...");
+    assert_eq!(lit(&frame(&mut wipe, 100, 4), Colour::RED), "##..");
+}
+
+#[test]
+fn wipe_with_no_step_time_still_moves() {
+    let mut wipe = Wipe::new(Colour::RED, Colour::BLACK, 0);
+
+    assert_eq!(lit(&frame(&mut wipe, 1, 2), Colour::RED), "##");
+}
+
+#[test]
+fn chase_moves_along_one_pixel_a_step() {
+    let mut chase = Chase::new(Colour::RED, Colour::BLACK, 100, 3);
+
+    let steps: Vec<String> = (0..4)
+        .map(|step| lit(&frame(&mut chase, step * 100, 7), Colour::RED))
+        .collect();
+
+    assert_eq!(steps, ["#..#..#", ".#..#..", "..#..#.", "#..#..#"]);
+}
+
+#[test]
+fn chase_with_no_spacing_lights_everything() {
+    let mut chase = Chase::new(Colour::RED, Colour::BLUE, 100, 0);
+
+    assert_eq!(lit(&frame(&mut chase, 250, 3), Colour::RED), "###");
+}
@@ -0,0 +1,152 @@
+//! The conversions the firmware's build script does on the `assets` folder
+
+#[allow(dead_code)]
+#[path = "../../build/assets.rs"]
+mod assets;
+#[allow(dead_code)]
+#[path = "../../build/bdf.rs"]
+mod bdf;
+#[allow(dead_code)]
+#[path = "../../build/images.rs"]
+mod images;
+#[path = "../../build/midi.rs"]
+mod midi;
+#[path = "../../build/songs.rs"]
+mod songs;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A fresh directory for each test, so they can run at the same time
+fn scratch_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("host-tests-{}", name));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+
+    directory
+}
+
+fn load_file(directory: &Path, name: &str, bytes: &[u8]) -> Result<images::Image, String> {
+    let path = directory.join(name);
+    fs::write(&path, bytes).unwrap();
+
+    images::load(&path)
+}
+
+#[test]
+fn raw_pbm() {
+    let directory = scratch_directory("raw-pbm");
+    let image = load_file(&directory, "image.pbm", b"P4\n10 2\n\xC0\x40\x00\x80").unwrap();
+
+    assert_eq!((image.width, image.height), (10, 2));
+    assert!(image.get(0, 0) && image.get(1, 0) && image.get(9, 0));
+    assert!(!image.get(2, 0) && !image.get(0, 1));
+    assert!(image.get(8, 1));
+}
+
+#[test]
+fn raw_pbm_ending_after_the_header_is_cut_short() {
+    let directory = scratch_directory("raw-pbm-no-data");
+
+    for bytes in [&b"P4\n8 1"[..], b"P4\n8 1\n", b"P4\n8 2\n\xFF"] {
+        let error = load_file(&directory, "image.pbm", bytes).err();
+
+        assert_eq!(error.as_deref(), Some("PBM image data is cut short"));
+    }
+}
+
+/// A format 0 MIDI file playing `notes` one after the other, as keys and lengths in ticks
+fn midi_file(ticks_per_quarter: u16, notes: &[(u8, u8)]) -> Vec<u8> {
+    let mut track = Vec::new();
+
+    for (key, ticks) in notes {
+        track.extend([0x00, 0x90, *key, 100]);
+        track.extend([*ticks, 0x80, *key, 0]);
+    }
+
+    track.extend([0x00, 0xFF, 0x2F, 0x00]);
+
+    let mut bytes = b"MThd".to_vec();
+    bytes.extend(6u32.to_be_bytes());
+    bytes.extend([0, 0, 0, 1]);
+    bytes.extend(ticks_per_quarter.to_be_bytes());
+    bytes.extend(b"MTrk");
+    bytes.extend((track.len() as u32).to_be_bytes());
+    bytes.extend(track);
+
+    bytes
+}
+
+/// The notes of the only song generated from `assets`, one per line
+fn song_notes(assets: &Path) -> Vec<String> {
+    let source = songs::generate(assets).unwrap();
+
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("(Note"))
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn long_notes_are_tied_across_lengths() {
+    let assets = scratch_directory("tied-notes");
+    fs::create_dir(assets.join("songs")).unwrap();
+
+    // With a tick per step, a dotted half and a quarter, then a dotted half and an eighth
+    fs::write(
+        assets.join("songs/tied.mid"),
+        midi_file(24, &[(60, 72), (62, 24), (64, 72 + 12)]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        song_notes(&assets),
+        [
+            "(Note::Midi(60), NoteLength::HALF.dotted()),",
+            "(Note::Midi(62), NoteLength::QUARTER),",
+            "(Note::Midi(64), NoteLength::HALF.dotted().tied()),",
+            "(Note::Midi(64), NoteLength::EIGHTH),",
+        ]
+    );
+}
+
+#[test]
+fn notes_longer_than_any_length_are_tied() {
+    let assets = scratch_directory("longer-than-any-length");
+    fs::create_dir(assets.join("songs")).unwrap();
+
+    // Five quarters can't be written as one length
+    fs::write(
+        assets.join("songs/held.mid"),
+        midi_file(24, &[(67, 120), (69, 24)]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        song_notes(&assets),
+        [
+            "(Note::Midi(67), NoteLength::WHOLE.tied()),",
+            "(Note::Midi(67), NoteLength::QUARTER),",
+            "(Note::Midi(69), NoteLength::QUARTER),",
+        ]
+    );
+}
+
+#[test]
+fn a_dotted_half_and_a_quarter_make_a_whole_note() {
+    let assets = scratch_directory("dotted-half-and-quarter");
+    fs::create_dir(assets.join("songs")).unwrap();
+
+    fs::write(
+        assets.join("songs/whole.mid"),
+        midi_file(24, &[(60, 72 + 24)]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        song_notes(&assets),
+        ["(Note::Midi(60), NoteLength::WHOLE),"]
+    );
+}
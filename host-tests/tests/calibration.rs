@@ -0,0 +1,107 @@
+use host_tests::calibration::{Calibration, CalibrationError, CALIBRATION_VERSION, RECORD_SIZE};
+
+fn calibration() -> Calibration {
+    Calibration {
+        x_min: 0.9501879,
+        x_max: 65525.46,
+        y_min: 3.8511074,
+        y_max: 65533.85,
+        z_min: -2.381914,
+        z_max: 65522.266,
+        x_offset: 32717,
+        y_offset: 32765,
+        z_offset: 32714,
+    }
+}
+
+#[test]
+fn round_trips_through_storage() {
+    let mut storage = [0xFF; RECORD_SIZE];
+
+    calibration().save(&mut storage);
+
+    assert_eq!(Calibration::load(&mut storage), Ok(calibration()));
+}
+
+#[test]
+fn record_starts_with_magic_and_version() {
+    let record = calibration().encode();
+
+    assert_eq!(&record[0..4], b"QMC6");
+    assert_eq!(record[4], CALIBRATION_VERSION);
+}
+
+#[test]
+fn blank_flash_is_not_found() {
+    let mut storage = [0xFF; RECORD_SIZE];
+
+    assert_eq!(
+        Calibration::load(&mut storage),
+        Err(CalibrationError::NotFound)
+    );
+}
+
+#[test]
+fn bad_magic_is_not_found() {
+    let mut record = calibration().encode();
+    record[0] = b'X';
+
+    assert_eq!(
+        Calibration::decode(&record),
+        Err(CalibrationError::NotFound)
+    );
+}
+
+#[test]
+fn other_versions_are_rejected() {
+    let mut record = calibration().encode();
+    record[4] = CALIBRATION_VERSION + 1;
+
+    assert_eq!(
+        Calibration::decode(&record),
+        Err(CalibrationError::UnsupportedVersion(
+            CALIBRATION_VERSION + 1
+        ))
+    );
+}
+
+#[test]
+fn any_corrupted_byte_fails_the_checksum() {
+    let record = calibration().encode();
+
+    // Everything after the magic and version is covered, including the checksum itself
+    for index in 5..RECORD_SIZE {
+        let mut corrupted = record;
+        corrupted[index] ^= 0x01;
+
+        assert_eq!(
+            Calibration::decode(&corrupted),
+            Err(CalibrationError::InvalidChecksum),
+            "byte {}",
+            index
+        );
+    }
+}
+
+#[test]
+fn checksum_is_crc32() {
+    let record = calibration().encode();
+    let checksum = u32::from_le_bytes(record[RECORD_SIZE - 4..].try_into().unwrap());
+
+    // Bitwise CRC-32 (IEEE 802.3), to check against a known implementation
+    let mut crc = 0xFFFF_FFFFu32;
+
+    for byte in &record[..RECORD_SIZE - 4] {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    assert_eq!(checksum, !crc);
+}
@@ -0,0 +1,116 @@
+use host_tests::calibration::{CalibrationEvent, Calibrator, CALIBRATION_SAMPLES};
+
+/// Feeds in the same sample until calibration finishes, returning how many it took
+fn hold_until_finished(calibrator: &mut Calibrator, x: f32, y: f32, z: f32) -> u16 {
+    for count in 1..=CALIBRATION_SAMPLES {
+        if let CalibrationEvent::Finished(_) = calibrator.update(x, y, z) {
+            return count;
+        }
+    }
+
+    panic!("calibration didn't finish");
+}
+
+#[test]
+fn first_sample_sets_the_extremes() {
+    let mut calibrator = Calibrator::new();
+
+    assert_eq!(
+        calibrator.update(0.0, 0.0, 0.0),
+        CalibrationEvent::Sample {
+            progress: 0,
+            total: CALIBRATION_SAMPLES,
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        }
+    );
+}
+
+#[test]
+fn progress_counts_samples_without_a_new_extreme() {
+    let mut calibrator = Calibrator::new();
+    calibrator.update(0.0, 0.0, 0.0);
+
+    for expected in 1..=5 {
+        match calibrator.update(0.0, 0.0, 0.0) {
+            CalibrationEvent::Sample {
+                progress, total, ..
+            } => {
+                assert_eq!(progress, expected);
+                assert_eq!(total, CALIBRATION_SAMPLES);
+            }
+            event => panic!("unexpected {:?}", event),
+        }
+    }
+}
+
+#[test]
+fn samples_are_filtered() {
+    let mut calibrator = Calibrator::new();
+    calibrator.update(0.0, 0.0, 0.0);
+
+    // Half of each new sample is mixed with half of the previous filtered value
+    match calibrator.update(100.0, -200.0, 40.0) {
+        CalibrationEvent::Sample { x, y, z, .. } => assert_eq!((x, y, z), (50.0, -100.0, 20.0)),
+        event => panic!("unexpected {:?}", event),
+    }
+}
+
+#[test]
+fn new_extreme_resets_progress() {
+    let mut calibrator = Calibrator::new();
+
+    for _ in 0..10 {
+        calibrator.update(0.0, 0.0, 0.0);
+    }
+
+    for (x, y, z) in [(10.0, 0.0, 0.0), (0.0, 0.0, -50.0)] {
+        match calibrator.update(x, y, z) {
+            CalibrationEvent::Sample { progress, .. } => assert_eq!(progress, 0),
+            event => panic!("unexpected {:?}", event),
+        }
+    }
+}
+
+#[test]
+fn finishes_once_the_extremes_stop_moving() {
+    let mut calibrator = Calibrator::new();
+    calibrator.update(0.0, 0.0, 0.0);
+
+    assert_eq!(
+        hold_until_finished(&mut calibrator, 0.0, 0.0, 0.0),
+        CALIBRATION_SAMPLES
+    );
+    assert!(calibrator.is_finished());
+
+    // It stays finished however much it's fed
+    assert!(matches!(
+        calibrator.update(1000.0, 1000.0, 1000.0),
+        CalibrationEvent::Finished(_)
+    ));
+}
+
+#[test]
+fn offsets_are_the_middle_of_each_axis() {
+    let mut calibrator = Calibrator::new();
+    calibrator.update(0.0, 0.0, 0.0);
+    calibrator.update(1000.0, 2000.0, 3000.0);
+
+    // Settling back towards 0 never goes below it, so can't extend the extremes
+    hold_until_finished(&mut calibrator, 0.0, 0.0, 0.0);
+
+    let calibration = calibrator.calibration();
+
+    assert_eq!((calibration.x_min, calibration.x_max), (0.0, 500.0));
+    assert_eq!((calibration.y_min, calibration.y_max), (0.0, 1000.0));
+    assert_eq!((calibration.z_min, calibration.z_max), (0.0, 1500.0));
+    assert_eq!(
+        (
+            calibration.x_offset,
+            calibration.y_offset,
+            calibration.z_offset
+        ),
+        (250, 500, 750)
+    );
+}
@@ -0,0 +1,115 @@
+use host_tests::colour::Colour;
+
+/// How far apart two hues are, the short way round
+fn hue_between(a: f32, b: f32) -> f32 {
+    let difference = (a - b).rem_euclid(360.0);
+
+    difference.min(360.0 - difference)
+}
+
+#[test]
+fn primaries_and_secondaries_from_hsv() {
+    for (hue, colour) in [
+        (0.0, Colour::RED),
+        (60.0, Colour::YELLOW),
+        (120.0, Colour::GREEN),
+        (180.0, Colour::CYAN),
+        (240.0, Colour::BLUE),
+        (300.0, Colour::MAGENTA),
+    ] {
+        assert_eq!(Colour::from_hsv(hue, 1.0, 1.0), colour, "{}°", hue);
+        assert_eq!(Colour::from_hsl(hue, 1.0, 0.5), colour, "{}°", hue);
+    }
+}
+
+#[test]
+fn hues_wrap_at_360() {
+    assert_eq!(Colour::from_hsv(360.0, 1.0, 1.0), Colour::RED);
+    assert_eq!(Colour::from_hsv(480.0, 1.0, 1.0), Colour::GREEN);
+    assert_eq!(Colour::from_hsv(720.0 + 240.0, 1.0, 1.0), Colour::BLUE);
+    assert_eq!(Colour::from_hsl(360.0 + 60.0, 1.0, 0.5), Colour::YELLOW);
+}
+
+#[test]
+fn negative_hues_count_back_from_360() {
+    assert_eq!(Colour::from_hsv(-120.0, 1.0, 1.0), Colour::BLUE);
+    assert_eq!(Colour::from_hsv(-360.0, 1.0, 1.0), Colour::RED);
+    assert_eq!(Colour::from_hsl(-300.0, 1.0, 0.5), Colour::YELLOW);
+}
+
+#[test]
+fn hues_just_either_side_of_red_are_red() {
+    // A tiny negative hue wraps to exactly 360°, one past the last sector
+    assert_eq!(Colour::from_hsv(-1e-6, 1.0, 1.0), Colour::RED);
+    assert_eq!(Colour::from_hsv(359.999, 1.0, 1.0), Colour::RED);
+}
+
+#[test]
+fn saturation_and_value_are_clamped() {
+    assert_eq!(Colour::from_hsv(0.0, 2.0, 2.0), Colour::RED);
+    assert_eq!(Colour::from_hsv(0.0, 0.0, 1.0), Colour::WHITE);
+    assert_eq!(Colour::from_hsv(0.0, 1.0, -1.0), Colour::BLACK);
+    assert_eq!(Colour::from_hsl(0.0, 1.0, 1.0), Colour::WHITE);
+    assert_eq!(Colour::from_hsl(0.0, 1.0, 0.0), Colour::BLACK);
+}
+
+#[test]
+fn primaries_to_hsv_and_hsl() {
+    for (colour, hue) in [
+        (Colour::RED, 0.0),
+        (Colour::GREEN, 120.0),
+        (Colour::BLUE, 240.0),
+        (Colour::MAGENTA, 300.0),
+    ] {
+        assert_eq!(colour.to_hsv(), (hue, 1.0, 1.0), "{:?}", colour);
+        assert_eq!(colour.to_hsl(), (hue, 1.0, 0.5), "{:?}", colour);
+    }
+
+    assert_eq!(Colour::WHITE.to_hsv(), (0.0, 0.0, 1.0));
+    assert_eq!(Colour::BLACK.to_hsl(), (0.0, 0.0, 0.0));
+}
+
+#[test]
+fn hues_just_below_red_stay_below_360() {
+    // More red than blue, so the hue is on the far side of 0°
+    let (hue, _, _) = Colour::new(255, 0, 1).to_hsv();
+
+    assert!((0.0..360.0).contains(&hue), "{}", hue);
+    assert!(hue_between(hue, 0.0) < 1.0, "{}", hue);
+}
+
+#[test]
+fn hsv_round_trips() {
+    for colour in [Colour::ORANGE, Colour::PURPLE, Colour::PINK, Colour::TEAL] {
+        let (hue, saturation, value) = colour.to_hsv();
+
+        assert_eq!(Colour::from_hsv(hue, saturation, value), colour);
+    }
+}
+
+#[test]
+fn lerp_and_scale() {
+    assert_eq!(Colour::RED.lerp(Colour::BLUE, 0.0), Colour::RED);
+    assert_eq!(
+        Colour::RED.lerp(Colour::BLUE, 0.5),
+        Colour::new(128, 0, 128)
+    );
+    assert_eq!(Colour::RED.lerp(Colour::BLUE, 2.0), Colour::BLUE);
+    assert_eq!(Colour::WHITE.scale(0.0), Colour::BLACK);
+    assert_eq!(Colour::WHITE.scale(1.0), Colour::WHITE);
+}
+
+#[test]
+fn gamma_correction_keeps_the_ends() {
+    assert_eq!(Colour::WHITE.gamma_corrected(), Colour::WHITE);
+    assert_eq!(Colour::BLACK.gamma_corrected(), Colour::BLACK);
+    assert!(Colour::new(128, 128, 128).gamma_corrected().r < 128);
+}
+
+#[test]
+fn converts_to_and_from_tuples() {
+    let rgb: (u8, u8, u8) = Colour::ORANGE.into();
+
+    assert_eq!(rgb, (255, 128, 0));
+    assert_eq!(Colour::from(rgb), Colour::ORANGE);
+}
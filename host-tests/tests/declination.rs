@@ -0,0 +1,46 @@
+//! Checks the truncated model against NOAA's full WMM2025 calculator
+
+use host_tests::declination::declination;
+
+/// The truncated model should be well within this of the full one, in degrees
+const TOLERANCE: f32 = 1.0;
+
+fn assert_declination(latitude: f32, longitude: f32, year: f32, expected: f32) {
+    let actual = declination(latitude, longitude, year);
+
+    assert!(
+        (actual - expected).abs() < TOLERANCE,
+        "Expected a declination of {}° at {}, {} in {}, got {}°",
+        expected,
+        latitude,
+        longitude,
+        year,
+        actual
+    );
+}
+
+#[test]
+fn brisbane_is_east() {
+    // NOAA gives about 11° E
+    assert_declination(-27.47, 153.03, 2025.0, 11.0);
+}
+
+#[test]
+fn reykjavik_is_west() {
+    // Far enough north that the higher degree terms matter more. NOAA gives about 11.5° W.
+    assert_declination(64.15, -21.94, 2025.0, -11.5);
+}
+
+#[test]
+fn changes_over_the_years() {
+    // Magnetic north is moving east over Iceland by around a third of a degree a year
+    let now = declination(64.15, -21.94, 2025.0);
+    let later = declination(64.15, -21.94, 2030.0);
+
+    assert!(
+        (1.0..2.0).contains(&(later - now)),
+        "Expected the declination to grow by 1° to 2° over five years, but it went from {}° to {}°",
+        now,
+        later
+    );
+}
@@ -0,0 +1,87 @@
+use host_tests::filter::{HeadingFilter, HeadingSmoother};
+
+/// How far apart two headings are, the short way round
+fn angle_between(a: f32, b: f32) -> f32 {
+    let difference = (a - b).rem_euclid(360.0);
+
+    difference.min(360.0 - difference)
+}
+
+fn assert_heading(actual: f32, expected: f32) {
+    assert!(
+        angle_between(actual, expected) < 0.01,
+        "Expected a heading of {}°, got {}°",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn moving_average_wraps_around_north() {
+    let mut smoother = HeadingSmoother::new(HeadingFilter::MovingAverage(2));
+
+    smoother.update(359.0);
+
+    assert_heading(smoother.update(1.0), 0.0);
+}
+
+#[test]
+fn low_pass_wraps_around_north() {
+    let mut smoother = HeadingSmoother::new(HeadingFilter::LowPass(0.5));
+
+    smoother.update(359.0);
+
+    assert_heading(smoother.update(1.0), 0.0);
+}
+
+#[test]
+fn headings_stay_below_360() {
+    let mut smoother = HeadingSmoother::new(HeadingFilter::MovingAverage(2));
+
+    smoother.update(358.0);
+    let heading = smoother.update(359.0);
+
+    assert!((0.0..360.0).contains(&heading));
+    assert_heading(heading, 358.5);
+}
+
+#[test]
+fn moving_average_forgets_old_headings() {
+    let mut smoother = HeadingSmoother::new(HeadingFilter::MovingAverage(2));
+
+    smoother.update(180.0);
+    smoother.update(10.0);
+
+    assert_heading(smoother.update(20.0), 15.0);
+}
+
+#[test]
+fn missing_readings_keep_the_previous_heading() {
+    for filter in [
+        HeadingFilter::None,
+        HeadingFilter::MovingAverage(4),
+        HeadingFilter::LowPass(0.5),
+    ] {
+        let mut smoother = HeadingSmoother::new(filter);
+
+        let heading = smoother.update(90.0);
+
+        assert_heading(smoother.update(f32::NAN), heading);
+    }
+}
+
+#[test]
+fn missing_first_reading_has_no_heading() {
+    let mut smoother = HeadingSmoother::new(HeadingFilter::LowPass(0.5));
+
+    assert!(smoother.update(f32::NAN).is_nan());
+}
+
+#[test]
+fn no_filter_passes_headings_through() {
+    let mut smoother = HeadingSmoother::new(HeadingFilter::None);
+
+    smoother.update(10.0);
+
+    assert_heading(smoother.update(200.0), 200.0);
+}
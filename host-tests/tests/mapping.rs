@@ -0,0 +1,201 @@
+use core::convert::Infallible;
+
+use host_tests::animation::PixelDisplay;
+use host_tests::colour::Colour;
+use host_tests::mapping::{show_value, BarGraph, ColourMap, Gradient, Thresholds};
+
+/// The proximity bar from `main`, which fills as something comes from 190mm to 60mm away
+fn proximity() -> BarGraph<Thresholds<3>> {
+    BarGraph::new(
+        190.0,
+        60.0,
+        Thresholds::new(
+            [
+                (60.0, Colour::RED),
+                (120.0, Colour::YELLOW),
+                (190.0, Colour::GREEN),
+            ],
+            Colour::BLACK,
+        ),
+    )
+}
+
+/// Three pixels like a single RGB module
+#[derive(Default)]
+struct MockDisplay {
+    pixels: [Colour; 3],
+    shows: usize,
+}
+
+impl PixelDisplay for MockDisplay {
+    type Error = Infallible;
+
+    fn pixel_count(&self) -> usize {
+        self.pixels.len()
+    }
+
+    fn set_colour(&mut self, index: usize, colour: Colour) {
+        self.pixels[index] = colour;
+    }
+
+    fn show(&mut self) -> Result<(), Infallible> {
+        self.shows += 1;
+
+        Ok(())
+    }
+}
+
+fn bar<M: ColourMap>(graph: &BarGraph<M>, value: f32) -> [Colour; 3] {
+    let mut display = MockDisplay::default();
+    graph.show(&mut display, value).unwrap();
+
+    assert_eq!(display.shows, 1);
+
+    display.pixels
+}
+
+#[test]
+fn gradient_holds_its_end_colours_beyond_the_stops() {
+    let gradient = Gradient::new([(0.0, Colour::BLUE), (100.0, Colour::RED)]);
+
+    assert_eq!(gradient.colour(-50.0), Colour::BLUE);
+    assert_eq!(gradient.colour(0.0), Colour::BLUE);
+    assert_eq!(gradient.colour(100.0), Colour::RED);
+    assert_eq!(gradient.colour(150.0), Colour::RED);
+}
+
+#[test]
+fn gradient_blends_between_stops() {
+    let gradient = Gradient::new([
+        (0.0, Colour::BLACK),
+        (10.0, Colour::WHITE),
+        (20.0, Colour::RED),
+    ]);
+
+    assert_eq!(gradient.colour(5.0), Colour::new(128, 128, 128));
+    assert_eq!(gradient.colour(10.0), Colour::WHITE);
+    assert_eq!(gradient.colour(15.0), Colour::new(255, 128, 128));
+}
+
+#[test]
+fn gradient_stops_at_the_same_value_make_a_hard_edge() {
+    let gradient = Gradient::new([
+        (0.0, Colour::GREEN),
+        (50.0, Colour::GREEN),
+        (50.0, Colour::RED),
+        (100.0, Colour::RED),
+    ]);
+
+    assert_eq!(gradient.colour(49.9), Colour::GREEN);
+    assert_eq!(gradient.colour(50.0), Colour::GREEN);
+    assert_eq!(gradient.colour(50.1), Colour::RED);
+}
+
+#[test]
+fn gradient_with_one_or_no_stops() {
+    assert_eq!(
+        Gradient::new([(5.0, Colour::TEAL)]).colour(-1.0),
+        Colour::TEAL
+    );
+    assert_eq!(
+        Gradient::new([(5.0, Colour::TEAL)]).colour(9.0),
+        Colour::TEAL
+    );
+    assert_eq!(Gradient::<0>::new([]).colour(5.0), Colour::BLACK);
+}
+
+#[test]
+fn thresholds_limits_belong_to_the_band_above() {
+    let thresholds = Thresholds::new([(10.0, Colour::RED), (20.0, Colour::YELLOW)], Colour::GREEN);
+
+    assert_eq!(thresholds.colour(-100.0), Colour::RED);
+    assert_eq!(thresholds.colour(9.99), Colour::RED);
+    assert_eq!(thresholds.colour(10.0), Colour::YELLOW);
+    assert_eq!(thresholds.colour(19.99), Colour::YELLOW);
+    assert_eq!(thresholds.colour(20.0), Colour::GREEN);
+    assert_eq!(thresholds.colour(f32::NAN), Colour::GREEN);
+}
+
+#[test]
+fn show_value_fills_every_pixel() {
+    let mut display = MockDisplay::default();
+    let thresholds = Thresholds::new([(10.0, Colour::RED)], Colour::GREEN);
+
+    show_value(&mut display, &thresholds, 5.0).unwrap();
+
+    assert_eq!(display.pixels, [Colour::RED; 3]);
+    assert_eq!(display.shows, 1);
+}
+
+#[test]
+fn bar_graph_fills_and_dims_the_last_pixel() {
+    let graph = BarGraph::new(0.0, 30.0, |_| Colour::WHITE);
+    let half = Colour::new(128, 128, 128);
+
+    assert_eq!(bar(&graph, 0.0), [Colour::BLACK; 3]);
+    assert_eq!(bar(&graph, 5.0), [half, Colour::BLACK, Colour::BLACK]);
+    assert_eq!(
+        bar(&graph, 10.0),
+        [Colour::WHITE, Colour::BLACK, Colour::BLACK]
+    );
+    assert_eq!(bar(&graph, 25.0), [Colour::WHITE, Colour::WHITE, half]);
+    assert_eq!(bar(&graph, 30.0), [Colour::WHITE; 3]);
+}
+
+#[test]
+fn bar_graph_clamps_values_outside_the_range() {
+    let graph = BarGraph::new(0.0, 30.0, |_| Colour::WHITE);
+
+    assert_eq!(bar(&graph, -10.0), [Colour::BLACK; 3]);
+    assert_eq!(bar(&graph, 1000.0), [Colour::WHITE; 3]);
+}
+
+#[test]
+fn bar_graph_with_no_range_or_value_shows_the_background() {
+    let mut graph = BarGraph::new(10.0, 10.0, |_| Colour::WHITE);
+    graph.background = Colour::BLUE;
+
+    assert_eq!(bar(&graph, 10.0), [Colour::BLUE; 3]);
+
+    graph.max = 20.0;
+
+    assert_eq!(bar(&graph, f32::NAN), [Colour::BLUE; 3]);
+}
+
+#[test]
+fn inverted_range_fills_as_the_value_falls() {
+    let graph = proximity();
+    let half_green = Colour::new(0, 128, 0);
+
+    // Nothing close enough to show
+    assert_eq!(bar(&graph, 500.0), [Colour::BLACK; 3]);
+    assert_eq!(bar(&graph, 190.0), [Colour::BLACK; 3]);
+
+    // Halfway in
+    assert_eq!(
+        bar(&graph, 125.0),
+        [Colour::GREEN, half_green, Colour::BLACK]
+    );
+
+    // All the way in, and closer still
+    assert_eq!(bar(&graph, 60.0), [Colour::YELLOW; 3]);
+    assert_eq!(bar(&graph, 59.0), [Colour::RED; 3]);
+    assert_eq!(bar(&graph, 0.0), [Colour::RED; 3]);
+}
+
+#[test]
+fn inverted_range_changes_colour_at_each_threshold() {
+    let graph = proximity();
+
+    // Each limit is the start of the next band out, so it's the colour further away. The first
+    // pixel is fully lit from a third of the way in, at about 147mm.
+    for (value, colour) in [
+        (140.0, Colour::GREEN),
+        (120.0, Colour::GREEN),
+        (119.9, Colour::YELLOW),
+        (60.0, Colour::YELLOW),
+        (59.9, Colour::RED),
+    ] {
+        assert_eq!(bar(&graph, value)[0], colour, "{}mm", value);
+    }
+}
@@ -0,0 +1,68 @@
+use host_tests::notes::{midi_to_frequency, Note, NoteLength, Song, ARTICULATION_MS, MAX_MIDI};
+
+#[test]
+fn from_midi_accepts_every_midi_note() {
+    assert_eq!(Note::from_midi(0), Some(Note::Midi(0)));
+    assert_eq!(Note::from_midi(60), Some(Note::C4));
+    assert_eq!(Note::from_midi(MAX_MIDI), Some(Note::Midi(127)));
+}
+
+#[test]
+fn from_midi_rejects_notes_above_127() {
+    assert_eq!(Note::from_midi(128), None);
+    assert_eq!(Note::from_midi(u8::MAX), None);
+}
+
+#[test]
+fn from_midi_works_in_constants() {
+    const HIGHEST: Option<Note> = Note::from_midi(MAX_MIDI);
+
+    assert_eq!(HIGHEST.and_then(|note| note.midi()), Some(127));
+}
+
+#[test]
+fn frequencies_are_tuned_to_a4() {
+    assert_eq!(Note::A4.frequency(), Some(440.0));
+    assert_eq!(Note::A5.frequency(), Some(880.0));
+    assert_eq!(Note::Rest.frequency(), None);
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn frequency_of_an_unchecked_note_above_127_panics_in_debug() {
+    midi_to_frequency(128);
+}
+
+#[test]
+fn repeated_notes_are_separated() {
+    const SONG: Song = Song::new(
+        120,
+        &[
+            (Note::C4, NoteLength::QUARTER),
+            (Note::C4, NoteLength::QUARTER),
+        ],
+    );
+
+    let timing = SONG.timing(0).unwrap();
+
+    assert_eq!(timing.length_ms, 500);
+    assert_eq!(timing.sounding_ms, 500 - ARTICULATION_MS);
+}
+
+#[test]
+fn tied_notes_are_held_into_the_next() {
+    const SONG: Song = Song::new(
+        120,
+        &[
+            (Note::C4, NoteLength::HALF.dotted().tied()),
+            (Note::C4, NoteLength::QUARTER),
+        ],
+    );
+
+    let first = SONG.timing(0).unwrap();
+    assert_eq!((first.sounding_ms, first.length_ms), (1500, 1500));
+
+    let second = SONG.timing(1).unwrap();
+    assert_eq!((second.sounding_ms, second.length_ms), (500, 500));
+    assert_eq!(SONG.duration_ms(), 2000);
+}
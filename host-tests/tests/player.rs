@@ -0,0 +1,214 @@
+use core::convert::Infallible;
+
+use host_tests::notes::{Note, NoteLength, Song, ARTICULATION_MS};
+use host_tests::player::{PlayerState, SongPlayer, ToneOutput};
+
+/// 500ms a quarter note
+const TEMPO: u16 = 120;
+
+static SCALE: Song = Song::new(
+    TEMPO,
+    &[
+        (Note::C4, NoteLength::QUARTER),
+        (Note::D4, NoteLength::EIGHTH),
+        (Note::Rest, NoteLength::QUARTER),
+        (Note::E4, NoteLength::HALF),
+    ],
+);
+
+static REPEATED: Song = Song::new(
+    TEMPO,
+    &[
+        (Note::C4, NoteLength::QUARTER),
+        (Note::C4, NoteLength::QUARTER),
+    ],
+);
+
+/// Remembers every tone it's asked to play
+#[derive(Default)]
+struct MockBuzzer {
+    tones: Vec<(Note, u16)>,
+}
+
+impl ToneOutput for MockBuzzer {
+    type Error = Infallible;
+
+    fn tone(&mut self, note: &Note, dur: u16) -> Result<(), Infallible> {
+        self.tones.push((*note, dur));
+
+        Ok(())
+    }
+}
+
+/// Ticks at each of `times`, returning which ones started a note
+fn tick_at(player: &mut SongPlayer, buzzer: &mut MockBuzzer, times: &[u32]) -> Vec<bool> {
+    times
+        .iter()
+        .map(|now_ms| player.tick(*now_ms, buzzer).unwrap())
+        .collect()
+}
+
+#[test]
+fn nothing_plays_until_play() {
+    let mut player = SongPlayer::new(&SCALE);
+    let mut buzzer = MockBuzzer::default();
+
+    assert_eq!(
+        tick_at(&mut player, &mut buzzer, &[0, 1000]),
+        [false, false]
+    );
+    assert!(buzzer.tones.is_empty());
+    assert_eq!(player.state(), PlayerState::Stopped);
+}
+
+#[test]
+fn each_note_starts_once_the_last_has_finished() {
+    let mut player = SongPlayer::new(&SCALE);
+    let mut buzzer = MockBuzzer::default();
+    player.play();
+
+    assert_eq!(
+        tick_at(&mut player, &mut buzzer, &[0, 499, 500, 749, 750]),
+        [true, false, true, false, true]
+    );
+    assert_eq!(buzzer.tones, [(Note::C4, 500), (Note::D4, 250)]);
+    assert_eq!(player.position(), 2);
+}
+
+#[test]
+fn rests_are_waited_out_without_a_tone() {
+    let mut player = SongPlayer::new(&SCALE);
+    let mut buzzer = MockBuzzer::default();
+    player.play();
+
+    tick_at(&mut player, &mut buzzer, &[0, 500, 750]);
+    assert_eq!(buzzer.tones.len(), 2);
+
+    assert_eq!(
+        tick_at(&mut player, &mut buzzer, &[1249, 1250]),
+        [false, true]
+    );
+    assert_eq!(buzzer.tones[2], (Note::E4, 1000));
+}
+
+#[test]
+fn repeated_notes_are_cut_short() {
+    let mut player = SongPlayer::new(&REPEATED);
+    let mut buzzer = MockBuzzer::default();
+    player.play();
+
+    tick_at(&mut player, &mut buzzer, &[0, 500]);
+
+    assert_eq!(
+        buzzer.tones,
+        [(Note::C4, 500 - ARTICULATION_MS as u16), (Note::C4, 500)]
+    );
+}
+
+#[test]
+fn late_ticks_start_notes_late() {
+    let mut player = SongPlayer::new(&SCALE);
+    let mut buzzer = MockBuzzer::default();
+    player.play();
+
+    // The second note is timed from when it actually started
+    assert_eq!(
+        tick_at(&mut player, &mut buzzer, &[0, 600, 849, 850]),
+        [true, true, false, true]
+    );
+}
+
+#[test]
+fn time_wrapping_around_is_fine() {
+    let mut player = SongPlayer::new(&SCALE);
+    let mut buzzer = MockBuzzer::default();
+    player.play();
+
+    let start = u32::MAX - 100;
+
+    assert_eq!(
+        tick_at(&mut player, &mut buzzer, &[start, 398, 399]),
+        [true, false, true]
+    );
+}
+
+#[test]
+fn pausing_holds_the_position() {
+    let mut player = SongPlayer::new(&SCALE);
+    let mut buzzer = MockBuzzer::default();
+    player.play();
+
+    tick_at(&mut player, &mut buzzer, &[0]);
+    player.pause();
+
+    // The first note plays out, but nothing follows it
+    assert_eq!(
+        tick_at(&mut player, &mut buzzer, &[500, 5000]),
+        [false, false]
+    );
+    assert_eq!(player.state(), PlayerState::Paused);
+    assert_eq!(player.position(), 1);
+
+    player.resume();
+
+    assert_eq!(tick_at(&mut player, &mut buzzer, &[6000]), [true]);
+    assert_eq!(buzzer.tones, [(Note::C4, 500), (Note::D4, 250)]);
+}
+
+#[test]
+fn stopping_goes_back_to_the_start() {
+    let mut player = SongPlayer::new(&SCALE);
+    let mut buzzer = MockBuzzer::default();
+    player.play();
+
+    tick_at(&mut player, &mut buzzer, &[0, 500]);
+    player.stop();
+    player.play();
+    tick_at(&mut player, &mut buzzer, &[600]);
+
+    assert_eq!(player.position(), 0);
+    assert_eq!(buzzer.tones[2], (Note::C4, 500));
+}
+
+#[test]
+fn songs_stop_at_the_end() {
+    let mut player = SongPlayer::new(&SCALE);
+    let mut buzzer = MockBuzzer::default();
+    player.play();
+
+    tick_at(&mut player, &mut buzzer, &[0, 500, 750, 1250]);
+
+    assert_eq!(tick_at(&mut player, &mut buzzer, &[2250]), [false]);
+    assert_eq!(player.state(), PlayerState::Stopped);
+    assert_eq!(player.position(), 0);
+    assert_eq!(buzzer.tones.len(), 3);
+}
+
+#[test]
+fn looping_starts_again_from_the_beginning() {
+    let mut player = SongPlayer::new(&SCALE);
+    let mut buzzer = MockBuzzer::default();
+    player.set_looping(true);
+    player.play();
+
+    tick_at(&mut player, &mut buzzer, &[0, 500, 750, 1250]);
+
+    assert_eq!(tick_at(&mut player, &mut buzzer, &[2250]), [true]);
+    assert!(player.is_playing());
+    assert_eq!(player.position(), 0);
+    assert_eq!(buzzer.tones[3], (Note::C4, 500));
+}
+
+#[test]
+fn skipping_plays_from_the_new_position() {
+    let mut player = SongPlayer::new(&SCALE);
+    let mut buzzer = MockBuzzer::default();
+    player.play();
+
+    tick_at(&mut player, &mut buzzer, &[0]);
+    player.set_position(3);
+
+    // The skip cuts the current note's wait short
+    assert_eq!(tick_at(&mut player, &mut buzzer, &[10]), [true]);
+    assert_eq!(buzzer.tones[1], (Note::E4, 1000));
+}
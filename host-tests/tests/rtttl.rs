@@ -0,0 +1,141 @@
+use host_tests::notes::{Note, NoteLength};
+use host_tests::rtttl::{Rtttl, RtttlError, RtttlNote};
+
+fn notes(text: &str) -> Vec<RtttlNote> {
+    Rtttl::parse(text).unwrap().notes().collect()
+}
+
+#[test]
+fn reads_the_name_and_defaults() {
+    let ringtone = Rtttl::parse("tune:d=8,o=5,b=120:c").unwrap();
+
+    assert_eq!(ringtone.name, "tune");
+    assert_eq!(ringtone.duration, 8);
+    assert_eq!(ringtone.octave, 5);
+    assert_eq!(ringtone.bpm, 120);
+}
+
+#[test]
+fn missing_defaults_come_from_the_specification() {
+    let ringtone = Rtttl::parse("tune::c").unwrap();
+
+    assert_eq!(ringtone.duration, 4);
+    assert_eq!(ringtone.octave, 6);
+    assert_eq!(ringtone.bpm, 63);
+    assert_eq!(notes("tune::c"), [RtttlNote::new(Some(84), 4, false)]);
+}
+
+#[test]
+fn notes_use_the_defaults_unless_they_give_their_own() {
+    assert_eq!(
+        notes("tune:d=4,o=5,b=120:c,8d,e6,16f7"),
+        [
+            RtttlNote::new(Some(72), 4, false),
+            RtttlNote::new(Some(74), 8, false),
+            RtttlNote::new(Some(88), 4, false),
+            RtttlNote::new(Some(101), 16, false),
+        ]
+    );
+}
+
+#[test]
+fn sharps_raise_a_semitone() {
+    assert_eq!(
+        notes("tune:d=4,o=5,b=120:c#,8f#6,a#"),
+        [
+            RtttlNote::new(Some(73), 4, false),
+            RtttlNote::new(Some(90), 8, false),
+            RtttlNote::new(Some(82), 4, false),
+        ]
+    );
+}
+
+#[test]
+fn dots_go_before_or_after_the_octave() {
+    assert_eq!(
+        notes("tune:d=4,o=5,b=120:2g.,2g.6,2g6.,p."),
+        [
+            RtttlNote::new(Some(79), 2, true),
+            RtttlNote::new(Some(91), 2, true),
+            RtttlNote::new(Some(91), 2, true),
+            RtttlNote::new(None, 4, true),
+        ]
+    );
+}
+
+#[test]
+fn h_is_b_and_letters_are_case_insensitive() {
+    assert_eq!(notes("tune:o=5:h,B"), notes("tune:o=5:b,b"));
+}
+
+#[test]
+fn whitespace_and_empty_notes_are_ignored() {
+    assert_eq!(
+        notes(" tune : d = 8 , o = 5 : c , , d "),
+        [
+            RtttlNote::new(Some(72), 8, false),
+            RtttlNote::new(Some(74), 8, false),
+        ]
+    );
+}
+
+#[test]
+fn missing_sections_are_rejected() {
+    assert_eq!(Rtttl::parse("tune").err(), Some(RtttlError::MissingSection));
+    assert_eq!(
+        Rtttl::parse("tune:d=4").err(),
+        Some(RtttlError::MissingSection)
+    );
+}
+
+#[test]
+fn bad_defaults_are_rejected() {
+    for text in [
+        "tune:d=3:c",
+        "tune:o=10:c",
+        "tune:b=0:c",
+        "tune:x=4:c",
+        "tune:d:c",
+        "tune:d=four:c",
+    ] {
+        assert_eq!(
+            Rtttl::parse(text).err(),
+            Some(RtttlError::BadDefault),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn bad_notes_are_rejected_by_index() {
+    for (text, index) in [
+        ("tune::c,x", 1),
+        ("tune::3c", 0),
+        ("tune::c,d,p#", 2),
+        ("tune::c,e#x", 1),
+        ("tune::c,..", 1),
+        // Above G9, the highest MIDI note
+        ("tune::g9,g#9", 1),
+    ] {
+        assert_eq!(
+            Rtttl::parse(text).err(),
+            Some(RtttlError::BadNote(index)),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn converts_into_notes_and_lengths() {
+    let [quarter, dotted_eighth, rest] = notes("tune:d=4,o=5,b=120:a,8a.,p")[..] else {
+        panic!("Expected three notes");
+    };
+
+    assert_eq!(Note::from(quarter), Note::A5);
+    assert_eq!(Note::from(rest), Note::Rest);
+
+    assert_eq!(NoteLength::from(quarter).to_ms(120), 500);
+    assert_eq!(NoteLength::from(dotted_eighth).to_ms(120), 375);
+}
@@ -0,0 +1,273 @@
+//! Draws the firmware's screens against a mock I2C bus and compares them with known good images
+//!
+//! The images are plain PBMs in `fixtures`, which any image viewer can open. After changing a
+//! screen on purpose, run `UPDATE_FIXTURES=1 cargo test` to write new ones, then check them by
+//! eye before committing them.
+
+use core::cell::RefCell;
+use core::convert::Infallible;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use embedded_hal::i2c::{ErrorType, I2c, Operation};
+use host_tests::piicodev_ssd1306::{
+    address_from_switch, PiicoDevSSD1306, ScrollDirection, ScrollInterval, Terminal,
+    ALTERNATE_ADDR, BASE_ADDR,
+};
+use host_tests::screens;
+
+const COLUMNS: usize = 128;
+const PAGES: usize = 8;
+
+/// Stands in for an SSD1306, keeping whatever is written into its RAM
+struct MockDisplay {
+    ram: [[u8; COLUMNS]; PAGES],
+    /// A command and its arguments, until they've all arrived
+    command: Vec<u8>,
+    columns: (u8, u8),
+    pages: (u8, u8),
+    column: u8,
+    page: u8,
+    /// The RAM can't be written while it's being scrolled
+    scrolling: bool,
+}
+
+impl MockDisplay {
+    fn new() -> Self {
+        Self {
+            ram: [[0; COLUMNS]; PAGES],
+            command: Vec::new(),
+            columns: (0, COLUMNS as u8 - 1),
+            pages: (0, PAGES as u8 - 1),
+            column: 0,
+            page: 0,
+            scrolling: false,
+        }
+    }
+
+    /// How many argument bytes follow each command the driver sends
+    fn argument_count(command: u8) -> usize {
+        match command {
+            0x20 | 0x23 | 0x81 | 0x8D | 0xA8 | 0xAD | 0xD3 | 0xD5 | 0xD6 | 0xD9 | 0xDA | 0xDB => 1,
+            0x21 | 0x22 | 0xA3 => 2,
+            0x29 | 0x2A => 5,
+            0x26 | 0x27 => 6,
+            _ => 0,
+        }
+    }
+
+    fn command_byte(&mut self, byte: u8) {
+        self.command.push(byte);
+
+        if self.command.len() <= Self::argument_count(self.command[0]) {
+            return;
+        }
+
+        // Only addressing matters for what ends up in RAM
+        match self.command[..] {
+            [0x21, start, end] => {
+                self.columns = (start, end);
+                self.column = start;
+            }
+            [0x22, start, end] => {
+                self.pages = (start, end);
+                self.page = start;
+            }
+            [0x2E] => self.scrolling = false,
+            [0x2F] => self.scrolling = true,
+            _ => {}
+        }
+
+        self.command.clear();
+    }
+
+    /// Horizontal addressing, wrapping around the window set by the last commands
+    fn data_byte(&mut self, byte: u8) {
+        assert!(!self.scrolling, "Display RAM written during a scroll");
+
+        self.ram[self.page as usize][self.column as usize] = byte;
+
+        if self.column < self.columns.1 {
+            self.column += 1;
+            return;
+        }
+
+        self.column = self.columns.0;
+        self.page = match self.page < self.pages.1 {
+            true => self.page + 1,
+            false => self.pages.0,
+        };
+    }
+}
+
+impl ErrorType for MockDisplay {
+    type Error = Infallible;
+}
+
+impl I2c for MockDisplay {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        assert_eq!(address, BASE_ADDR);
+
+        for operation in operations {
+            let Operation::Write(bytes) = operation else {
+                panic!("The display is never read from");
+            };
+
+            match bytes[0] {
+                // A single command
+                0x80 => self.command_byte(bytes[1]),
+                0x00 => bytes[1..].iter().for_each(|byte| self.command_byte(*byte)),
+                0x40 => bytes[1..].iter().for_each(|byte| self.data_byte(*byte)),
+                control => panic!("Unknown control byte 0x{:02X}", control),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// Checks the framebuffer against `fixtures/<name>.pbm`, and that `show` sent all of it
+///
+/// With `UPDATE_FIXTURES` set, the image is written to the fixture instead.
+///
+fn assert_screen(name: &str, display: &PiicoDevSSD1306<MockDisplay>, bus: &RefCell<MockDisplay>) {
+    let framebuffer = display.framebuffer();
+
+    for page in 0..framebuffer.display_size().pages() {
+        assert_eq!(
+            framebuffer.page(page),
+            &bus.borrow().ram[page as usize][..],
+            "Page {} on the display doesn't match the framebuffer",
+            page
+        );
+    }
+
+    let mut image = String::new();
+    framebuffer.write_pbm(&mut image).unwrap();
+
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "fixtures",
+        &format!("{}.pbm", name),
+    ]
+    .iter()
+    .collect();
+
+    if env::var_os("UPDATE_FIXTURES").is_some() {
+        fs::write(&path, image).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|error| panic!("Couldn't read {}: {}", path.display(), error));
+
+    assert!(
+        image == expected,
+        "{} has changed, this is what's on the display now:\n{}",
+        path.display(),
+        image
+    );
+}
+
+#[test]
+fn splash() {
+    let bus = RefCell::new(MockDisplay::new());
+    let mut display = PiicoDevSSD1306::new(None, None, &bus);
+    display.init().unwrap();
+
+    screens::splash(&mut display).unwrap();
+    display.show().unwrap();
+
+    assert_screen("splash", &display, &bus);
+}
+
+#[test]
+fn readings_scroll_up_the_terminal() {
+    let bus = RefCell::new(MockDisplay::new());
+    let mut display = PiicoDevSSD1306::new(None, None, &bus);
+    display.init().unwrap();
+
+    let mut terminal = Terminal::new(&mut display);
+    terminal.set_cursor(true);
+
+    // Five rounds of two lines is more than the 8 rows fit
+    for round in 0..5 {
+        let round = round as f32;
+
+        screens::log_readings(
+            &mut terminal,
+            21.5 + round,
+            101_325.0 - 100.0 * round,
+            45.0 + 2.0 * round,
+            12.0 + round,
+            250 * (round as u16 + 1),
+        )
+        .unwrap();
+    }
+
+    terminal.show().unwrap();
+
+    assert_screen("readings", &display, &bus);
+}
+
+#[test]
+fn distance_gauge() {
+    let bus = RefCell::new(MockDisplay::new());
+    let mut display = PiicoDevSSD1306::new(None, None, &bus);
+    display.init().unwrap();
+
+    screens::distance(display.framebuffer_mut(), 500);
+    display.show().unwrap();
+
+    assert_screen("distance", &display, &bus);
+}
+
+#[test]
+fn distance_gauge_redraws_over_itself() {
+    let bus = RefCell::new(MockDisplay::new());
+    let mut display = PiicoDevSSD1306::new(None, None, &bus);
+    display.init().unwrap();
+
+    // Only the columns that changed are sent the second time
+    screens::distance(display.framebuffer_mut(), 1800);
+    display.show().unwrap();
+    screens::distance(display.framebuffer_mut(), 500);
+    display.show().unwrap();
+
+    assert_screen("distance", &display, &bus);
+}
+
+#[test]
+fn switch_picks_the_address() {
+    assert_eq!(address_from_switch(0), BASE_ADDR);
+    assert_eq!(address_from_switch(1), ALTERNATE_ADDR);
+}
+
+#[test]
+fn showing_stops_a_scroll_and_sends_everything() {
+    let bus = RefCell::new(MockDisplay::new());
+    let mut display = PiicoDevSSD1306::new(None, None, &bus);
+    display.init().unwrap();
+
+    screens::distance(display.framebuffer_mut(), 500);
+    display.show().unwrap();
+
+    display
+        .scroll_horizontal(ScrollDirection::Left, 0, 7, ScrollInterval::Frames2)
+        .unwrap();
+    assert!(bus.borrow().scrolling);
+
+    // The scroll moved everything in RAM, so all of it needs sending again
+    bus.borrow_mut().ram = [[0; COLUMNS]; PAGES];
+    display.show().unwrap();
+
+    assert!(!display.is_scrolling());
+    assert!(!bus.borrow().scrolling);
+    assert_screen("distance", &display, &bus);
+}
@@ -1,6 +1,7 @@
 MEMORY {
     BOOT2 : ORIGIN = 0x10000000, LENGTH = 0x200
-    FLASH : ORIGIN = 0x10000200, LENGTH = 2048K - 0x100
+    /* The last 4K sector is kept out of FLASH for the magnetometer's calibration */
+    FLASH : ORIGIN = 0x10000200, LENGTH = 2048K - 0x200 - 4K
     RAM   : ORIGIN = 0x20000000, LENGTH = 256K
 
     /** From the pico-sdk memory mapping */
@@ -0,0 +1,137 @@
+//! Raw access to a sector of the Pico's external QSPI flash
+//!
+//! The flash is memory-mapped through the XIP cache, so reading is just a pointer read. Writing
+//! has to take the flash out of XIP mode, which means the code doing it (and everything it
+//! touches) must live in RAM until XIP is turned back on.
+
+use rp_pico::hal::rom_data;
+
+use crate::piicodev_qmc6310::calibration::{CalibrationStorage, RECORD_SIZE};
+
+/// Where the flash is mapped into the address space
+const XIP_BASE: u32 = 0x1000_0000;
+
+/// Total size of the flash chip on the Pico
+pub const FLASH_SIZE: u32 = 2048 * 1024;
+
+/// Smallest erasable region of flash
+pub const SECTOR_SIZE: usize = 4096;
+
+/// Smallest programmable region of flash
+pub const PAGE_SIZE: usize = 256;
+
+/// The second stage bootloader at the start of flash, which sets up the fast QSPI XIP mode
+const BOOT2_SIZE: usize = 256;
+
+/// Largest erase the ROM is allowed to use, and the command that does it
+const BLOCK_SIZE: u32 = 65536;
+const BLOCK_ERASE_COMMAND: u8 = 0xD8;
+
+/// ROM routines used while XIP is disabled, and boot2 to turn it back on. They have to be looked
+/// up beforehand, as the lookup code lives in flash.
+struct FlashFunctions {
+    connect_internal_flash: unsafe extern "C" fn(),
+    flash_exit_xip: unsafe extern "C" fn(),
+    flash_range_erase: unsafe extern "C" fn(u32, usize, u32, u8),
+    flash_range_program: unsafe extern "C" fn(u32, *const u8, usize),
+    flash_flush_cache: unsafe extern "C" fn(),
+    enter_xip: unsafe extern "C" fn(),
+}
+
+///
+/// Erases the sector at `offset` and programs its first page from `page`
+///
+/// Placed in RAM, as flash cannot be read while it is being written
+///
+#[inline(never)]
+#[link_section = ".data.ram_func"]
+unsafe fn erase_and_program(offset: u32, page: *const u8, functions: &FlashFunctions) {
+    (functions.connect_internal_flash)();
+    (functions.flash_exit_xip)();
+    (functions.flash_range_erase)(offset, SECTOR_SIZE, BLOCK_SIZE, BLOCK_ERASE_COMMAND);
+    (functions.flash_range_program)(offset, page, PAGE_SIZE);
+    (functions.flash_flush_cache)();
+    (functions.enter_xip)();
+}
+
+///
+/// A single 4K sector of flash reserved for storing our own data
+///
+/// The sector must be left out of the FLASH region in memory.x so the program never gets linked
+/// over it.
+///
+pub struct FlashSector {
+    offset: u32,
+}
+
+impl FlashSector {
+    /// `offset` is from the start of flash and must be sector aligned
+    pub const fn new(offset: u32) -> Self {
+        Self { offset }
+    }
+
+    /// The last sector of flash
+    pub const fn last() -> Self {
+        Self::new(FLASH_SIZE - SECTOR_SIZE as u32)
+    }
+
+    /// Reads from the start of the sector into `buffer`
+    pub fn read(&self, buffer: &mut [u8]) {
+        let start = (XIP_BASE + self.offset) as *const u8;
+
+        for (i, byte) in buffer.iter_mut().take(SECTOR_SIZE).enumerate() {
+            *byte = unsafe { core::ptr::read_volatile(start.add(i)) };
+        }
+    }
+
+    ///
+    /// Erases the sector and writes `data` to its start. Anything past the first page is ignored.
+    ///
+    /// Interrupts are disabled for the duration of the write, which takes tens of milliseconds.
+    ///
+    pub fn write(&mut self, data: &[u8]) {
+        // The ROM can only program whole pages, and the source must be in RAM
+        let mut page = [0xFF; PAGE_SIZE];
+        let length = data.len().min(PAGE_SIZE);
+        page[..length].copy_from_slice(&data[..length]);
+
+        // The ROM's own `flash_enter_cmd_xip` only sets up slow single bit reads, so boot2 is
+        // copied into RAM to be run again once the flash is written, the same way rp2040-flash
+        // does it
+        let mut boot2 = [0u32; BOOT2_SIZE / 4];
+
+        for (i, word) in boot2.iter_mut().enumerate() {
+            *word = unsafe { core::ptr::read_volatile((XIP_BASE as *const u32).add(i)) };
+        }
+
+        // The lowest bit marks the code as Thumb, the only instruction set the M0+ has
+        let enter_xip = unsafe {
+            core::mem::transmute::<*const u8, unsafe extern "C" fn()>(
+                (boot2.as_ptr() as *const u8).add(1),
+            )
+        };
+
+        let functions = FlashFunctions {
+            connect_internal_flash: rom_data::connect_internal_flash::ptr(),
+            flash_exit_xip: rom_data::flash_exit_xip::ptr(),
+            flash_range_erase: rom_data::flash_range_erase::ptr(),
+            flash_range_program: rom_data::flash_range_program::ptr(),
+            flash_flush_cache: rom_data::flash_flush_cache::ptr(),
+            enter_xip,
+        };
+
+        cortex_m::interrupt::free(|_| unsafe {
+            erase_and_program(self.offset, page.as_ptr(), &functions);
+        });
+    }
+}
+
+impl CalibrationStorage for FlashSector {
+    fn read_record(&mut self, record: &mut [u8; RECORD_SIZE]) {
+        self.read(record);
+    }
+
+    fn write_record(&mut self, record: &[u8; RECORD_SIZE]) {
+        self.write(record);
+    }
+}
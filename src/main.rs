@@ -10,11 +10,6 @@ use core::fmt::Write;
 use cortex_m::delay::Delay;
 use defmt::*;
 use defmt_rtt as _;
-use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyleBuilder};
-use embedded_graphics::pixelcolor::BinaryColor;
-use embedded_graphics::prelude::*;
-use embedded_graphics::primitives::{PrimitiveStyleBuilder, Rectangle};
-use embedded_graphics::text::{Alignment, Text};
 use embedded_hal::digital::InputPin;
 use embedded_hal::pwm::SetDutyCycle;
 use fugit::RateExtU32;
@@ -37,15 +32,18 @@ use bsp::hal::watchdog::Watchdog;
 use bsp::hal::Timer;
 use bsp::Pins;
 use servo::Servo;
-use ssd1306::{prelude::*, I2CDisplayInterface, Ssd1306};
 
+mod flash;
 mod i2c;
 mod piicodev_bme280;
 mod piicodev_buzzer;
+mod piicodev_error;
 mod piicodev_qmc6310;
 mod piicodev_rgb;
 mod piicodev_ssd1306;
 mod piicodev_vl53l1x;
+mod ring_buffer;
+mod screens;
 mod servo;
 mod uart;
 
@@ -184,28 +182,49 @@ fn main() -> ! {
         125_000_000.Hz(),
     );
 
-    // let declination = 12.64; // Brisbane
+    // use crate::flash::FlashSector;
+    // use crate::piicodev_qmc6310::calibration::CalibrationEvent;
+    // use crate::piicodev_qmc6310::declination::declination;
+    // use crate::piicodev_qmc6310::filter::HeadingFilter;
+    //
+    // let declination = declination(-27.47, 153.03, 2025.8); // Brisbane
     // let mut magnetometer = PiicoDevQMC6310::new(None, Some(GaussRange::Gauss1200), declination);
     //
-    // if magnetometer.init(&mut i2c).is_err() {
+    // // Calibration is kept in the last sector of flash, which memory.x reserves
+    // let mut calibration_storage = FlashSector::last();
+    //
+    // if magnetometer.init(&mut i2c, &mut calibration_storage).is_err() {
     //     writeln!(uart, "Failed to initialise magnetometer").unwrap();
     // }
     // delay.delay_ms(5);
     //
     // delay.delay_ms(1000);
     //
-    // // if magnetometer
-    // //     .calibrate(false, &mut i2c, &mut uart, &mut delay)
-    // //     .is_err()
-    // // {
+    // // writeln!(uart, "*** Calibrating. Slowly rotate your sensor until the bar is full").unwrap();
+    // //
+    // // let calibration = magnetometer.calibrate(
+    // //     &mut i2c,
+    // //     &mut delay,
+    // //     &mut calibration_storage,
+    // //     |event| {
+    // //         if let CalibrationEvent::Sample { progress, total, .. } = event {
+    // //             let filled = (10 * progress / total) as usize;
+    // //             write!(uart, "\r[{:*<filled$}{: <empty$}]", "", "", empty = 10 - filled).unwrap();
+    // //         }
+    // //     },
+    // // );
+    // //
+    // // if calibration.is_err() {
     // //     writeln!(uart, "Failed to calibrate magnetometer").unwrap();
     // // }
     //
+    // magnetometer.set_heading_filter(HeadingFilter::MovingAverage(8));
+    //
     // loop {
-    //     let reading = magnetometer.read_polar(&mut i2c, &mut uart);
+    //     let heading = magnetometer.read_heading(&mut i2c);
     //
-    //     if let Ok(reading) = reading {
-    //         writeln!(uart, "Polar: {}°", reading.polar as u16).unwrap();
+    //     if let Ok(heading) = heading {
+    //         writeln!(uart, "Heading: {}°", heading as u16).unwrap();
     //     } else {
     //         writeln!(uart, "Failed to read magnetometer").unwrap();
     //     }
@@ -213,83 +232,36 @@ fn main() -> ! {
     //     delay.delay_ms(100);
     // }
     //
-    // let interface = I2CDisplayInterface::new(i2c);
+    // // Turn IO devices into shared pointers
+    // let i2c_cell = RefCell::new(i2c);
+    // let uart_cell = RefCell::new(uart);
+    // let delay_cell = RefCell::new(delay);
     //
-    // let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
-    //     .into_buffered_graphics_mode();
+    // let mut display = PiicoDevSSD1306::new(None, None, &i2c_cell);
     //
-    // display.init().unwrap();
+    // // A second display, with its address jumper closed, on the same bus
+    // use crate::piicodev_ssd1306::address_from_switch;
     //
-    // // NOTE: SSD1306 only supports binary colours: on and off (white and black)
-    // let white_text_style = MonoTextStyleBuilder::new()
-    //     .font(&FONT_6X10)
-    //     .text_color(BinaryColor::On)
-    //     .build();
-    //
-    // let black_text_style = MonoTextStyleBuilder::new()
-    //     .font(&FONT_6X10)
-    //     .text_color(BinaryColor::Off)
-    //     .build();
-    //
-    // let white_rectangle_style = PrimitiveStyleBuilder::new()
-    //     .fill_color(BinaryColor::On)
-    //     .stroke_color(BinaryColor::On)
-    //     .stroke_width(3)
-    //     .build();
-    //
-    // Rectangle::new(Point::zero(), Size::new(128, 64))
-    //     .into_styled(white_rectangle_style)
-    //     .draw(&mut display)
-    //     .unwrap();
+    // let mut dashboard = PiicoDevSSD1306::new(Some(address_from_switch(1)), None, &i2c_cell);
+    // dashboard.init().unwrap();
     //
-    // Text::with_alignment(
-    //     "80% of boys have",
-    //     Point::new(64, 8),
-    //     black_text_style,
-    //     Alignment::Center,
-    // )
-    // .draw(&mut display)
-    // .unwrap();
-    //
-    // Text::with_alignment(
-    //     "girlfriends",
-    //     Point::new(64, 24),
-    //     black_text_style,
-    //     Alignment::Center,
-    // )
-    // .draw(&mut display)
-    // .unwrap();
-    //
-    // Text::with_alignment(
-    //     "rest 20% are having",
-    //     Point::new(64, 40),
-    //     black_text_style,
-    //     Alignment::Center,
-    // )
-    // .draw(&mut display)
-    // .unwrap();
-    //
-    // Text::with_alignment(
-    //     "a brain",
-    //     Point::new(64, 56),
-    //     black_text_style,
-    //     Alignment::Center,
-    // )
-    // .draw(&mut display)
-    // .unwrap();
-    //
-    // display.flush().unwrap();
+    // display.init().unwrap();
     //
-    // // Turn IO devices into shared pointers
-    // let i2c_cell = RefCell::new(i2c);
-    // let uart_cell = RefCell::new(uart);
-    // let delay_cell = RefCell::new(delay);
+    // // The same screens are drawn by the host tests, see `host-tests/fixtures`
+    // screens::splash(&mut display).unwrap();
+    //
+    // display.show().unwrap();
     //
     // let mut distance_sensor = PiicoDevVL53L1X::new(None, &i2c_cell, &delay_cell);
     // distance_sensor.init().unwrap();
     //
     // // Set up the RGB device
-    // let mut rgb = PiicoDevRGB::new(&i2c_cell);
+    // let mut rgb = PiicoDevRGB::new(None, &i2c_cell);
+    // rgb.init().unwrap();
+    //
+    // // A second module, with its address changed once beforehand using `set_i2c_addr`, can be
+    // // chained on to make one row of six LEDs for the animations
+    // // let mut rgb = RgbStrip::new([rgb, PiicoDevRGB::new(Some(0x1F), &i2c_cell)]);
     //
     // // Turn the LED on
     // rgb.power_led(true).unwrap();
@@ -301,13 +273,18 @@ fn main() -> ! {
     // buzzer.init().unwrap();
     // buzzer.volume(BuzzerVolume::Low).unwrap();
     //
+    // // Play a ringtone from the assets folder on startup
+    // use crate::piicodev_buzzer::ringtones::ODE_TO_JOY;
+    //
+    // buzzer
+    //     .play_ringtone(ODE_TO_JOY.bpm, ODE_TO_JOY.notes.iter().copied())
+    //     .unwrap();
+    //
     // // Initialise the temperature sensor
     // let mut temperature_sensor = PiicoDevBME280::new(&i2c_cell, &delay_cell);
     // temperature_sensor.init().unwrap();
     //
-    // // Increases evey time the sensor reads close OR reads far consecutively
     // let mut last_is_close = false;
-    // let mut same_reading_index = 0;
     //
     // // How long to wait until the next reading
     // const NEAR_DELAY: u32 = 20;
@@ -315,90 +292,99 @@ fn main() -> ! {
     //
     // let mut next_delay;
     //
-    // const COLOUR_MAP: [(u8, u8, u8); 6] = [
-    //     (255, 0, 0),
-    //     (0, 255, 0),
-    //     (0, 0, 255),
-    //     (255, 255, 0),
-    //     (0, 255, 255),
-    //     (255, 0, 255),
-    // ];
-    //
-    // // Play repeated song
-    // let mut note_index = 0;
-    // let song = HARMONY;
-    //
-    // // Set up the OLED display
-    // let mut oled = PiicoDevSSD1306::new(&i2c_cell);
-    // oled.init().unwrap();
-    // oled.fill(OLEDColour::WHITE);
-    //
-    // for i in 64..0 {
-    //     oled.pixel(i, i, OLEDColour::BLACK);
+    // use crate::piicodev_rgb::animation::Animator;
+    // use crate::piicodev_rgb::colour::Colour;
+    // use crate::piicodev_rgb::effects::Rainbow;
+    // use crate::piicodev_rgb::mapping::{BarGraph, Thresholds};
+    //
+    // // Colours run along the LEDs while someone is close
+    // let timer = Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
+    // let mut near_animation = Animator::new(Rainbow::new(1000, 120.0));
+    //
+    // // Otherwise they fill up like a traffic light as someone approaches
+    // let proximity = BarGraph::new(
+    //     190.0,
+    //     60.0,
+    //     Thresholds::new(
+    //         [(60.0, Colour::RED), (120.0, Colour::YELLOW), (190.0, Colour::GREEN)],
+    //         Colour::BLACK,
+    //     ),
+    // );
+    //
+    // // Play repeated song while someone is close. Songs converted from MIDI files in the assets
+    // // folder, like `FRERE_JACQUES`, can be played the same way.
+    // use crate::piicodev_buzzer::player::SongPlayer;
+    //
+    // let mut song_player = SongPlayer::new(&HARMONY);
+    // song_player.set_looping(true);
+    //
+    // display.framebuffer_mut().fill(OLEDColour::WHITE);
+    //
+    // for i in 0..64 {
+    //     display.framebuffer_mut().pixel(i, i, OLEDColour::BLACK);
     // }
     //
-    // oled.show().unwrap();
+    // display.show().unwrap();
+    //
+    // // Readings are also logged to the display
+    // use crate::piicodev_ssd1306::Terminal;
+    //
+    // let mut terminal = Terminal::new(&mut display);
+    // terminal.set_cursor(true);
     //
     // loop {
     //     let reading = distance_sensor.read().unwrap();
     //
     //     let mut uart = uart_cell.borrow_mut();
     //
+    //     // Typing 's' on the console sends a screenshot of the display
+    //     let mut command = [0; 1];
+    //
+    //     if let Ok(1) = uart.read_raw(&mut command) {
+    //         if command[0] == b's' {
+    //             terminal.display().framebuffer().write_screenshot(&mut *uart).unwrap();
+    //         }
+    //     }
+    //
     //     let is_close = reading < 100;
     //
     //     // Have we gone from close to near, or from near to close?
     //     let did_change = is_close != last_is_close;
     //
     //     if did_change {
-    //         same_reading_index = 0;
-    //     } else {
-    //         same_reading_index += 1;
+    //         if is_close {
+    //             // The animation starts from the beginning each time someone comes close
+    //             near_animation.restart();
+    //             song_player.play();
+    //         } else {
+    //             song_player.pause();
+    //             buzzer.no_tone().unwrap();
+    //         }
     //     }
     //
     //     last_is_close = is_close;
     //
+    //     let now_ms = (timer.get_counter().ticks() / 1000) as u32;
+    //
     //     if is_close {
     //         writeln!(uart, "GAMERS DETECTED!!!!! JULIA, KAFFY, SCRYBID").unwrap();
     //         // Set brightness
     //         rgb.set_brightness(20).unwrap();
     //
-    //         let left = COLOUR_MAP[same_reading_index % 6];
-    //         let middle = COLOUR_MAP[(same_reading_index + 1) % 6];
-    //         let right = COLOUR_MAP[(same_reading_index + 2) % 6];
-    //
-    //         // Set colours
-    //         rgb.set_pixel(0, left);
-    //         rgb.set_pixel(1, middle);
-    //         rgb.set_pixel(2, right);
+    //         near_animation.tick(now_ms, &mut rgb).unwrap();
     //
     //         next_delay = NEAR_DELAY;
-    //
-    //         // Play the next note of the song
-    //         let (note, duration) = HARMONY[note_index];
-    //         // buzzer.tone(&note, duration).unwrap();
-    //
-    //         note_index += 1;
-    //
-    //         let is_at_end_of_song = note_index >= song.len();
-    //
-    //         if is_at_end_of_song {
-    //             // Restart the song
-    //             note_index = 0;
-    //         }
     //     } else {
     //         writeln!(uart, "No gaming detected in the vicinity...").unwrap();
     //         // Set brightness
     //         rgb.set_brightness(5).unwrap();
     //
-    //         // Set colours
-    //         rgb.set_pixel(0, (255, 0, 0));
-    //         rgb.set_pixel(1, (0, 255, 0));
-    //         rgb.set_pixel(2, (0, 0, 255));
+    //         proximity.show(&mut rgb, reading as f32).unwrap();
     //
     //         next_delay = FAR_DELAY;
     //     }
     //
-    //     rgb.show().unwrap();
+    //     song_player.tick(now_ms, &mut buzzer).unwrap();
     //
     //     let readings = temperature_sensor.values().unwrap();
     //     let altitude = temperature_sensor.altitude(None).unwrap();
@@ -409,6 +395,21 @@ fn main() -> ! {
     //     )
     //     .unwrap();
     //
+    //     screens::log_readings(
+    //         &mut terminal,
+    //         readings.temperature,
+    //         readings.pressure,
+    //         readings.humidity,
+    //         altitude,
+    //         reading,
+    //     )
+    //     .unwrap();
+    //     terminal.show().unwrap();
+    //
+    //     // Distance goes on the second display
+    //     screens::distance(dashboard.framebuffer_mut(), reading);
+    //     dashboard.show().unwrap();
+    //
     //     {
     //         let mut delay = delay_cell.borrow_mut();
     //         delay.delay_ms(next_delay);
@@ -1,2 +1,6 @@
 pub mod notes;
 pub mod piicodev_buzzer;
+pub mod player;
+pub mod ringtones;
+pub mod rtttl;
+pub mod songs;
@@ -1,285 +1,481 @@
 use libm::powf;
 
-#[derive(Clone, Copy)]
+use super::rtttl::RtttlNote;
+
+/// The highest MIDI note number, G9
+pub const MAX_MIDI: u8 = 127;
+
+/// When a note is followed by the same one, it's cut short by this much so the two can be heard
+pub const ARTICULATION_MS: u32 = 30;
+
+/// Names every note of an octave, starting from the MIDI number of its C
+macro_rules! octave {
+    (
+        $c:expr,
+        $C:ident, $CS:ident, $D:ident, $DS:ident, $E:ident, $F:ident,
+        $FS:ident, $G:ident, $GS:ident, $A:ident, $AS:ident, $B:ident
+    ) => {
+        pub const $C: Note = Note::Midi($c);
+        pub const $CS: Note = Note::Midi($c + 1);
+        pub const $D: Note = Note::Midi($c + 2);
+        pub const $DS: Note = Note::Midi($c + 3);
+        pub const $E: Note = Note::Midi($c + 4);
+        pub const $F: Note = Note::Midi($c + 5);
+        pub const $FS: Note = Note::Midi($c + 6);
+        pub const $G: Note = Note::Midi($c + 7);
+        pub const $GS: Note = Note::Midi($c + 8);
+        pub const $A: Note = Note::Midi($c + 9);
+        pub const $AS: Note = Note::Midi($c + 10);
+        pub const $B: Note = Note::Midi($c + 11);
+    };
+}
+
+///
+/// A pitch to play, or a rest
+///
+/// Pitches are MIDI note numbers from 0 to 127, with middle C (`C4`) as 60. The constants name
+/// every note from `C0` to `B8`, with sharps ending in `S`. Use `Note::from_midi` for any other
+/// pitch, which checks that it's in range.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Note {
-    Rest = 0,
-    C3 = 48,
-    C3S = 49,
-    D3 = 50,
-    D3S = 51,
-    E3 = 52,
-    F3 = 53,
-    F3S = 54,
-    G3 = 55,
-    G3S = 56,
-    A3 = 57,
-    A3S = 58,
-    B3 = 59,
-    C4 = 60,
-    C4S = 61,
-    D4 = 62,
-    D4S = 63,
-    E4 = 64,
-    F4 = 65,
-    F4S = 66,
-    G4 = 67,
-    G4S = 68,
-    A4 = 69,
-    B4 = 71,
-    C5 = 72,
-    C5S = 73,
-    D5 = 74,
-    D5S = 75,
-    E5 = 76,
-    F5 = 77,
-    F5S = 78,
-    G5 = 79,
-    G5S = 80,
-    A5 = 81,
-    A5S = 82,
-    B5 = 83,
-    C6 = 84,
-    C6S = 85,
-    D6 = 86,
-    D6S = 87,
-    E6 = 88,
-    F6 = 89,
-    F6S = 90,
-    G6 = 91,
-    G6S = 92,
-    A6 = 93,
-    B6 = 94,
-    B6S = 95,
+    Rest,
+    Midi(u8),
+}
+
+impl Note {
+    octave!(12, C0, C0S, D0, D0S, E0, F0, F0S, G0, G0S, A0, A0S, B0);
+    octave!(24, C1, C1S, D1, D1S, E1, F1, F1S, G1, G1S, A1, A1S, B1);
+    octave!(36, C2, C2S, D2, D2S, E2, F2, F2S, G2, G2S, A2, A2S, B2);
+    octave!(48, C3, C3S, D3, D3S, E3, F3, F3S, G3, G3S, A3, A3S, B3);
+    octave!(60, C4, C4S, D4, D4S, E4, F4, F4S, G4, G4S, A4, A4S, B4);
+    octave!(72, C5, C5S, D5, D5S, E5, F5, F5S, G5, G5S, A5, A5S, B5);
+    octave!(84, C6, C6S, D6, D6S, E6, F6, F6S, G6, G6S, A6, A6S, B6);
+    octave!(96, C7, C7S, D7, D7S, E7, F7, F7S, G7, G7S, A7, A7S, B7);
+    octave!(108, C8, C8S, D8, D8S, E8, F8, F8S, G8, G8S, A8, A8S, B8);
+
+    /// A pitch from its MIDI note number, or `None` if it's above `MAX_MIDI`
+    pub const fn from_midi(midi: u8) -> Option<Self> {
+        if midi > MAX_MIDI {
+            return None;
+        }
+
+        Some(Self::Midi(midi))
+    }
+
+    /// The MIDI note number, or `None` for a rest
+    pub fn midi(&self) -> Option<u8> {
+        match self {
+            Self::Rest => None,
+            Self::Midi(midi) => Some(*midi),
+        }
+    }
+
+    /// The frequency in Hz, or `None` for a rest
+    pub fn frequency(&self) -> Option<f32> {
+        self.midi().map(midi_to_frequency)
+    }
 }
 
+/// Rests come out as 0Hz, which keeps the buzzer quiet
 pub fn note_to_frequency(key: &Note) -> f32 {
-    let note: f32 = *key as u32 as f32;
+    key.frequency().unwrap_or(0.0)
+}
+
+/// Frequency of a MIDI note number, tuned to A4 at 440Hz
+pub fn midi_to_frequency(midi: u8) -> f32 {
+    debug_assert!(midi <= MAX_MIDI, "MIDI note {} is out of range", midi);
+
+    let note: f32 = midi as f32;
 
     // 2 ** ((key - 69) / 12) * 440;
     let x2: f32 = 2.0;
     powf(x2, (note - 69.0) / 12.0) * 440.0
 }
 
-pub const EIGHT_MELODIES: [(Note, u16); 41] = [
-    (Note::C5, 1000),
-    (Note::D5, 1000),
-    (Note::E5, 1000),
-    (Note::G5, 1000),
-    (Note::D5, 4000),
-    (Note::C6, 1000),
-    (Note::B5, 1000),
-    (Note::A5, 1000),
-    (Note::E5, 1000),
-    (Note::G5, 4000),
-    (Note::A5, 1000),
-    (Note::B5, 1000),
-    (Note::C6, 1000),
-    (Note::G5, 2000),
-    (Note::C5, 3500),
-    (Note::F5, 1000),
-    (Note::E5, 1000),
-    (Note::C5, 1000),
-    (Note::G4, 5000),
-    (Note::A4, 2000),
-    (Note::B4, 2000),
-    (Note::C5, 2000),
-    (Note::F5, 2000),
-    (Note::E5, 1000),
-    (Note::F5, 1000),
-    (Note::G5, 1000),
-    (Note::E5, 1000),
-    (Note::D5, 1000),
-    (Note::E5, 1000),
-    (Note::F5, 1000),
-    (Note::D5, 1000),
-    (Note::A4, 1000),
-    (Note::F5, 1000),
-    (Note::E5, 1000),
-    (Note::G4, 1000),
-    (Note::D5, 2000),
-    (Note::B4, 2000),
-    (Note::C5, 2000),
-    (Note::G4, 1000),
-    (Note::D5, 1000),
-    (Note::C5, 4000),
-];
-
-pub const HARMONY: [(Note, u16); 172] = [
-    // First bit
-    (Note::A5, 1000),
-    (Note::B5, 1000),
-    (Note::C6, 1000),
-    (Note::A5, 1000),
-    (Note::B5, 1000),
-    (Note::C6, 1000),
-    (Note::E6, 1000),
-    (Note::D6, 1000),
-    (Note::G5, 3000),
-    (Note::D6, 1000),
-    (Note::C6, 1000),
-    (Note::B5, 2000),
-    (Note::C6, 1000),
-    (Note::B5, 1000),
-    (Note::A5, 2000),
-    (Note::G5, 3000),
-    (Note::A5, 1000),
-    (Note::B5, 1000),
-    (Note::C6, 1000),
-    (Note::A5, 1000),
-    (Note::B5, 1000),
-    (Note::C6, 1000),
-    (Note::E6, 1000),
-    (Note::D6, 1000),
-    (Note::G5, 3000),
-    (Note::D6, 1000),
-    (Note::C6, 1000),
-    (Note::B5, 2000),
-    (Note::G5, 2000),
-    (Note::A5, 1000),
-    // Second bit
-    (Note::A5, 2000),
-    (Note::A5, 1000),
-    (Note::A5, 2000),
-    (Note::A5, 2000),
-    (Note::G5, 2000),
-    (Note::E5, 1000),
-    (Note::D5, 2000),
-    (Note::C5, 2000),
-    (Note::D5, 2000),
-    (Note::E5, 2000),
-    (Note::D5, 2000),
-    (Note::C5, 2000),
-    (Note::B4, 2000),
-    (Note::A5, 2000),
-    (Note::A5, 1000),
-    (Note::A5, 2000),
-    (Note::A5, 2000),
-    (Note::E5, 2000),
-    (Note::G5, 1000),
-    (Note::F5, 1000),
-    (Note::E5, 2000),
-    (Note::C5, 2000),
-    (Note::A4, 2000),
-    // Third bit
-    (Note::A5, 1000),
-    (Note::A5, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::C5S, 1000),
-    (Note::C5S, 1000),
-    (Note::A5, 1000),
-    (Note::A5, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::C5S, 1000),
-    (Note::C5S, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::B4, 1000),
-    (Note::B4, 1000),
-    (Note::G4, 1000),
-    (Note::G4, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::B4, 1000),
-    (Note::B4, 1000),
-    (Note::G4, 1000),
-    (Note::G4, 1000),
-    (Note::A5, 1000),
-    (Note::A5, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::C5S, 1000),
-    (Note::C5S, 1000),
-    (Note::A5, 1000),
-    (Note::A5, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::C5S, 1000),
-    (Note::C5S, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::B4, 1000),
-    (Note::B4, 1000),
-    (Note::G4, 1000),
-    (Note::G4, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::B4, 1000),
-    (Note::B4, 1000),
-    (Note::G4, 1000),
-    (Note::G4, 1000),
-    // Fourth bit
-    (Note::A5, 2000),
-    (Note::A5, 1000),
-    (Note::A5, 2000),
-    (Note::A5, 2000),
-    (Note::G5, 2000),
-    (Note::E5, 1000),
-    (Note::D5, 2000),
-    (Note::C5, 2000),
-    (Note::D5, 2000),
-    (Note::E5, 2000),
-    (Note::D5, 2000),
-    (Note::C5, 2000),
-    (Note::B4, 2000),
-    (Note::A5, 2000),
-    (Note::A5, 1000),
-    (Note::A5, 2000),
-    (Note::A5, 2000),
-    (Note::E5, 2000),
-    (Note::G5, 1000),
-    (Note::F5, 1000),
-    (Note::E5, 2000),
-    (Note::C5, 2000),
-    (Note::A4, 2000),
-    // Fifth bit
-    (Note::A5, 1000),
-    (Note::A5, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::C5S, 1000),
-    (Note::C5S, 1000),
-    (Note::A5, 1000),
-    (Note::A5, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::C5S, 1000),
-    (Note::C5S, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::B4, 1000),
-    (Note::B4, 1000),
-    (Note::G4, 1000),
-    (Note::G4, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::B4, 1000),
-    (Note::B4, 1000),
-    (Note::G4, 1000),
-    (Note::G4, 1000),
-    (Note::A5, 1000),
-    (Note::A5, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::C5S, 1000),
-    (Note::C5S, 1000),
-    (Note::A5, 1000),
-    (Note::A5, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::C5, 1000),
-    (Note::C5, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::B4, 1000),
-    (Note::B4, 1000),
-    (Note::G4, 1000),
-    (Note::G4, 1000),
-    (Note::E5, 1000),
-    (Note::E5, 1000),
-    (Note::B4, 1000),
-    (Note::B4, 1000),
-    (Note::G4, 1000),
-    (Note::G4, 1000),
-];
+///
+/// How long a note lasts, as a fraction of a whole note
+///
+/// Dotted notes are half as long again, and triplets fit three in the time of two.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NoteLength {
+    /// 1 for a whole note, 4 for a quarter note and so on
+    pub division: u8,
+    pub dotted: bool,
+    pub triplet: bool,
+    /// Carries straight on into the next note, for lengths that take more than one to write
+    pub tied: bool,
+}
+
+impl NoteLength {
+    pub const WHOLE: Self = Self::new(1);
+    pub const HALF: Self = Self::new(2);
+    pub const QUARTER: Self = Self::new(4);
+    pub const EIGHTH: Self = Self::new(8);
+    pub const SIXTEENTH: Self = Self::new(16);
+    pub const THIRTY_SECOND: Self = Self::new(32);
+
+    pub const fn new(division: u8) -> Self {
+        Self {
+            division,
+            dotted: false,
+            triplet: false,
+            tied: false,
+        }
+    }
+
+    pub const fn dotted(self) -> Self {
+        Self {
+            dotted: true,
+            ..self
+        }
+    }
+
+    pub const fn triplet(self) -> Self {
+        Self {
+            triplet: true,
+            ..self
+        }
+    }
+
+    pub const fn tied(self) -> Self {
+        Self { tied: true, ..self }
+    }
+
+    /// How long the note lasts at `bpm` quarter notes per minute
+    pub fn to_ms(self, bpm: u16) -> u32 {
+        let mut numerator = 4 * 60_000;
+        let mut denominator = bpm.max(1) as u32 * self.division.max(1) as u32;
+
+        if self.dotted {
+            numerator *= 3;
+            denominator *= 2;
+        }
+
+        if self.triplet {
+            numerator *= 2;
+            denominator *= 3;
+        }
+
+        numerator / denominator
+    }
+}
+
+impl From<RtttlNote> for Note {
+    fn from(note: RtttlNote) -> Self {
+        note.midi.map_or(Self::Rest, Self::Midi)
+    }
+}
+
+impl From<RtttlNote> for NoteLength {
+    fn from(note: RtttlNote) -> Self {
+        Self {
+            division: note.duration,
+            dotted: note.dotted,
+            triplet: false,
+            tied: false,
+        }
+    }
+}
+
+/// When a note sounds within a song
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NoteTiming {
+    pub note: Note,
+    /// How long the buzzer plays the note for
+    pub sounding_ms: u32,
+    /// How long until the next note starts
+    pub length_ms: u32,
+}
+
+/// How long `note` sounds for out of `length_ms`, leaving a gap before `next` if it's the same
+pub fn sounding_ms(note: Note, length_ms: u32, next: Option<Note>) -> u32 {
+    if note != Note::Rest && next == Some(note) {
+        length_ms.saturating_sub(ARTICULATION_MS.min(length_ms / 2))
+    } else {
+        length_ms
+    }
+}
+
+/// A tune, with its notes' lengths relative to the tempo
+pub struct Song {
+    /// Quarter notes per minute
+    pub tempo: u16,
+    pub notes: &'static [(Note, NoteLength)],
+}
+
+impl Song {
+    pub const fn new(tempo: u16, notes: &'static [(Note, NoteLength)]) -> Self {
+        Self { tempo, notes }
+    }
+
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// When the note at `index` sounds, or `None` past the end of the song
+    pub fn timing(&self, index: usize) -> Option<NoteTiming> {
+        let (note, length) = *self.notes.get(index)?;
+        let length_ms = length.to_ms(self.tempo);
+        let next = self.notes.get(index + 1).map(|(next, _)| *next);
+
+        // A tied note is held right up to the next one, rather than leaving a gap
+        let sounding_ms = match length.tied {
+            true => length_ms,
+            false => sounding_ms(note, length_ms, next),
+        };
+
+        Some(NoteTiming {
+            note,
+            sounding_ms,
+            length_ms,
+        })
+    }
+
+    /// How long the whole song takes to play
+    pub fn duration_ms(&self) -> u32 {
+        self.notes
+            .iter()
+            .map(|(_, length)| length.to_ms(self.tempo))
+            .sum()
+    }
+}
+
+// The lengths were originally in ms, played at a quarter speed with 1000 as an eighth note, so
+// the odd ones are made up with rests
+pub const EIGHT_MELODIES: Song = Song::new(
+    120,
+    &[
+        (Note::C5, NoteLength::EIGHTH),
+        (Note::D5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::G5, NoteLength::EIGHTH),
+        (Note::D5, NoteLength::HALF),
+        (Note::C6, NoteLength::EIGHTH),
+        (Note::B5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::G5, NoteLength::HALF),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::B5, NoteLength::EIGHTH),
+        (Note::C6, NoteLength::EIGHTH),
+        (Note::G5, NoteLength::QUARTER),
+        (Note::C5, NoteLength::QUARTER.dotted()),
+        (Note::Rest, NoteLength::SIXTEENTH),
+        (Note::F5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::C5, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::HALF),
+        (Note::Rest, NoteLength::EIGHTH),
+        (Note::A4, NoteLength::QUARTER),
+        (Note::B4, NoteLength::QUARTER),
+        (Note::C5, NoteLength::QUARTER),
+        (Note::F5, NoteLength::QUARTER),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::F5, NoteLength::EIGHTH),
+        (Note::G5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::D5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::F5, NoteLength::EIGHTH),
+        (Note::D5, NoteLength::EIGHTH),
+        (Note::A4, NoteLength::EIGHTH),
+        (Note::F5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::D5, NoteLength::QUARTER),
+        (Note::B4, NoteLength::QUARTER),
+        (Note::C5, NoteLength::QUARTER),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::D5, NoteLength::EIGHTH),
+        (Note::C5, NoteLength::HALF),
+    ],
+);
+
+pub const HARMONY: Song = Song::new(
+    120,
+    &[
+        // First bit
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::B5, NoteLength::EIGHTH),
+        (Note::C6, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::B5, NoteLength::EIGHTH),
+        (Note::C6, NoteLength::EIGHTH),
+        (Note::E6, NoteLength::EIGHTH),
+        (Note::D6, NoteLength::EIGHTH),
+        (Note::G5, NoteLength::QUARTER.dotted()),
+        (Note::D6, NoteLength::EIGHTH),
+        (Note::C6, NoteLength::EIGHTH),
+        (Note::B5, NoteLength::QUARTER),
+        (Note::C6, NoteLength::EIGHTH),
+        (Note::B5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::G5, NoteLength::QUARTER.dotted()),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::B5, NoteLength::EIGHTH),
+        (Note::C6, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::B5, NoteLength::EIGHTH),
+        (Note::C6, NoteLength::EIGHTH),
+        (Note::E6, NoteLength::EIGHTH),
+        (Note::D6, NoteLength::EIGHTH),
+        (Note::G5, NoteLength::QUARTER.dotted()),
+        (Note::D6, NoteLength::EIGHTH),
+        (Note::C6, NoteLength::EIGHTH),
+        (Note::B5, NoteLength::QUARTER),
+        (Note::G5, NoteLength::QUARTER),
+        (Note::A5, NoteLength::EIGHTH),
+        // Second bit
+        (Note::A5, NoteLength::QUARTER),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::G5, NoteLength::QUARTER),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::D5, NoteLength::QUARTER),
+        (Note::C5, NoteLength::QUARTER),
+        (Note::D5, NoteLength::QUARTER),
+        (Note::E5, NoteLength::QUARTER),
+        (Note::D5, NoteLength::QUARTER),
+        (Note::C5, NoteLength::QUARTER),
+        (Note::B4, NoteLength::QUARTER),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::E5, NoteLength::QUARTER),
+        (Note::G5, NoteLength::EIGHTH),
+        (Note::F5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::QUARTER),
+        (Note::C5, NoteLength::QUARTER),
+        (Note::A4, NoteLength::QUARTER),
+        // Third bit
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        // Fourth bit
+        (Note::A5, NoteLength::QUARTER),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::G5, NoteLength::QUARTER),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::D5, NoteLength::QUARTER),
+        (Note::C5, NoteLength::QUARTER),
+        (Note::D5, NoteLength::QUARTER),
+        (Note::E5, NoteLength::QUARTER),
+        (Note::D5, NoteLength::QUARTER),
+        (Note::C5, NoteLength::QUARTER),
+        (Note::B4, NoteLength::QUARTER),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::A5, NoteLength::QUARTER),
+        (Note::E5, NoteLength::QUARTER),
+        (Note::G5, NoteLength::EIGHTH),
+        (Note::F5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::QUARTER),
+        (Note::C5, NoteLength::QUARTER),
+        (Note::A4, NoteLength::QUARTER),
+        // Fifth bit
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::C5S, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::A5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::C5, NoteLength::EIGHTH),
+        (Note::C5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::E5, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::B4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+        (Note::G4, NoteLength::EIGHTH),
+    ],
+);
@@ -7,11 +7,14 @@ use bsp::hal::i2c::Error;
 use embedded_hal::i2c::I2c;
 
 use crate::i2c::I2CHandler;
+use crate::piicodev_error::PiicoDevError;
 
-use super::notes::{note_to_frequency, Note};
+use super::notes::{note_to_frequency, sounding_ms, Note, NoteLength, NoteTiming, Song};
+use super::player::ToneOutput;
+use super::rtttl::RtttlNote;
 
 const BASE_ADDR: u8 = 0x5C;
-const _DEV_ID: u8 = 0x51;
+const DEV_ID: u8 = 0x51;
 const REG_DEV_ID: u8 = 0x11;
 const REG_STATUS: u8 = 0x01;
 const REG_FIRM_MAJ: u8 = 0x02;
@@ -51,18 +54,64 @@ impl<'i2c, 'delay> PiicoDevBuzzer<'i2c, 'delay> {
         Self { addr, i2c, delay }
     }
 
-    pub fn init(&mut self) -> Result<(), Error> {
+    /// Checks the device at the address really is a buzzer, then turns on its power LED
+    pub fn init(&mut self) -> Result<(), PiicoDevError> {
+        let found = self.read_id()?;
+
+        if found != DEV_ID {
+            return Err(PiicoDevError::UnexpectedDeviceId {
+                expected: DEV_ID,
+                found,
+            });
+        }
+
+        self.power_led(true)?;
+
+        Ok(())
+    }
+
+    /// Reads a single register
+    fn read_register(&mut self, register: u8) -> Result<u8, Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        let mut value = [0; 1];
+        i2c.write_read(self.addr, &[register], &mut value)?;
+
+        Ok(value[0])
+    }
+
+    pub fn read_id(&mut self) -> Result<u8, Error> {
+        self.read_register(REG_DEV_ID)
+    }
+
+    /// Major and minor firmware version
+    pub fn read_firmware(&mut self) -> Result<(u8, u8), Error> {
+        let major = self.read_register(REG_FIRM_MAJ)?;
+        let minor = self.read_register(REG_FIRM_MIN)?;
+
+        Ok((major, minor))
+    }
+
+    pub fn read_status(&mut self) -> Result<u8, Error> {
+        self.read_register(REG_STATUS)
+    }
+
+    pub fn power_led(&mut self, on: bool) -> Result<(), Error> {
         let mut i2c = self.i2c.borrow_mut();
 
-        i2c.write(BASE_ADDR, &[REG_LED, 0x01])
+        i2c.write(self.addr, &[REG_LED, on as u8])
     }
 
     pub fn tone(&mut self, note: &Note, dur: u16) -> Result<(), Error> {
+        self.tone_frequency(note_to_frequency(note) as u16, dur)
+    }
+
+    /// Plays `hz` for `dur` milliseconds, for sounds that aren't notes
+    pub fn tone_frequency(&mut self, hz: u16, dur: u16) -> Result<(), Error> {
         let mut i2c = self.i2c.borrow_mut();
 
         // Using u16 as the buzzer module requires 2 big-endian bytes to be passed in as payload
-        let freq = note_to_frequency(note) as u16;
-        let frequency: &[u8] = &freq.to_be_bytes();
+        let frequency: &[u8] = &hz.to_be_bytes();
         let duration: &[u8] = &dur.to_be_bytes();
 
         // [address, frequency1, frequency2, duration1, duration2]
@@ -74,7 +123,12 @@ impl<'i2c, 'delay> PiicoDevBuzzer<'i2c, 'delay> {
             duration[1],
         ];
 
-        i2c.write(BASE_ADDR, &payload)
+        i2c.write(self.addr, &payload)
+    }
+
+    /// Stops whatever is playing straight away
+    pub fn no_tone(&mut self) -> Result<(), Error> {
+        self.tone_frequency(0, 0)
     }
 
     pub fn volume(&mut self, vol: BuzzerVolume) -> Result<(), Error> {
@@ -83,53 +137,60 @@ impl<'i2c, 'delay> PiicoDevBuzzer<'i2c, 'delay> {
         i2c.write(self.addr, &[REG_VOLUME, vol.into()])
     }
 
-    // pub fn read_firmware(&mut self) -> Result<[u8; 2], Error> {
-    //     let mut i2c = self.i2c.borrow_mut();
-    //
-    //     let mut v: [u8; 2] = [0, 0];
-    //     i2c.read(self.addr, &mut v).map(|()| v)
-    // }
-    //
-    // pub fn read_status(&mut self) -> Result<[u8; 1], Error> {
-    //     let mut i2c = self.i2c.borrow_mut();
-    //
-    //     let mut status: [u8; 1] = [REG_STATUS];
-    //     i2c.read(self.addr, &mut status).map(|()| status)
-    // }
-    //
-    // pub fn read_id(&mut self) -> Result<u8, Error> {
-    //     let mut i2c = self.i2c.borrow_mut();
-    //
-    //     let mut id_buffer: [u8; 1] = [REG_DEV_ID];
-    //     i2c.read(self.addr, &mut id_buffer).map(|()| id_buffer[0])
-    // }
-    //
-    // pub fn power_led(&mut self, on: bool) -> Result<(), Error> {
-    //     let mut i2c = self.i2c.borrow_mut();
-    //
-    //     i2c.write(
-    //         self.addr,
-    //         &[
-    //             REG_LED,
-    //             match on {
-    //                 false => 0,
-    //                 true => 1,
-    //             },
-    //         ],
-    //     )
-    // }
-
-    pub fn play_song(&mut self, notes: &[(Note, u16)]) -> Result<(), Error> {
-        let mut delay = self.delay.borrow_mut();
-
-        for (tone, duration) in notes {
-            let note_duration = *duration / 4;
-
-            self.tone(tone, note_duration)?;
-
-            delay.delay_ms(note_duration as u32)
+    /// Plays a note for its sounding time, then waits for the rest of its length
+    fn play_timed(&mut self, timing: NoteTiming) -> Result<(), Error> {
+        if timing.note != Note::Rest {
+            let dur = timing.sounding_ms.min(u16::MAX as u32) as u16;
+            self.tone(&timing.note, dur)?;
+        }
+
+        self.delay.borrow_mut().delay_ms(timing.length_ms);
+
+        Ok(())
+    }
+
+    /// Plays a whole song, blocking until it's done. Use a `SongPlayer` to keep doing other things.
+    pub fn play_song(&mut self, song: &Song) -> Result<(), Error> {
+        for index in 0..song.len() {
+            if let Some(timing) = song.timing(index) {
+                self.play_timed(timing)?;
+            }
         }
 
         Ok(())
     }
+
+    ///
+    /// Plays RTTTL notes at `bpm`, blocking until they're done
+    ///
+    /// Takes the notes of either a `Ringtone` from `assets` or an `Rtttl` read at runtime.
+    ///
+    pub fn play_ringtone<I: IntoIterator<Item = RtttlNote>>(
+        &mut self,
+        bpm: u16,
+        notes: I,
+    ) -> Result<(), Error> {
+        let mut notes = notes.into_iter().peekable();
+
+        while let Some(note) = notes.next() {
+            let length_ms = NoteLength::from(note).to_ms(bpm);
+            let next = notes.peek().map(|next| Note::from(*next));
+
+            self.play_timed(NoteTiming {
+                note: note.into(),
+                sounding_ms: sounding_ms(note.into(), length_ms, next),
+                length_ms,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'i2c, 'delay> ToneOutput for PiicoDevBuzzer<'i2c, 'delay> {
+    type Error = Error;
+
+    fn tone(&mut self, note: &Note, dur: u16) -> Result<(), Error> {
+        PiicoDevBuzzer::tone(self, note, dur)
+    }
 }
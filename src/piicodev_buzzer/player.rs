@@ -0,0 +1,152 @@
+use super::notes::{Note, Song};
+
+/// Something that can sound a note, such as a buzzer
+pub trait ToneOutput {
+    type Error;
+
+    /// Starts `note` playing for `dur` milliseconds, without waiting for it to finish
+    fn tone(&mut self, note: &Note, dur: u16) -> Result<(), Self::Error>;
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PlayerState {
+    Stopped,
+    Playing,
+    Paused,
+}
+
+///
+/// Plays a song without blocking
+///
+/// Call `tick` as often as the main loop allows, and each note is sent to the `ToneOutput` once
+/// the one before it has finished.
+///
+pub struct SongPlayer<'song> {
+    song: &'song Song,
+    state: PlayerState,
+    looping: bool,
+    /// Index of the note playing, or next to be played
+    position: usize,
+    /// When the current note started and how long it lasts, if it has been sent to the output
+    current: Option<(u32, u32)>,
+}
+
+impl<'song> SongPlayer<'song> {
+    pub fn new(song: &'song Song) -> Self {
+        Self {
+            song,
+            state: PlayerState::Stopped,
+            looping: false,
+            position: 0,
+            current: None,
+        }
+    }
+
+    pub fn state(&self) -> PlayerState {
+        self.state
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.state == PlayerState::Playing
+    }
+
+    /// Stops the current song and swaps in another, ready to be played from the start
+    pub fn set_song(&mut self, song: &'song Song) {
+        self.song = song;
+        self.stop();
+    }
+
+    /// Starts the song, or carries on from where it was paused
+    pub fn play(&mut self) {
+        self.state = PlayerState::Playing;
+    }
+
+    ///
+    /// Holds the song at the end of the current note
+    ///
+    /// A note that has already started plays to the end, unless it's cut short with
+    /// `PiicoDevBuzzer::no_tone`.
+    ///
+    pub fn pause(&mut self) {
+        if self.state == PlayerState::Playing {
+            self.state = PlayerState::Paused;
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if self.state == PlayerState::Paused {
+            self.state = PlayerState::Playing;
+        }
+    }
+
+    /// Goes back to the start, waiting for `play`
+    pub fn stop(&mut self) {
+        self.state = PlayerState::Stopped;
+        self.position = 0;
+        self.current = None;
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Whether to start again from the beginning once the song has finished
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Index in the song of the note playing, or the next to be played
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Skips to the note at `position`, which is played on the next tick
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position.min(self.song.len());
+        self.current = None;
+    }
+
+    ///
+    /// Starts the next note once the current one is over, returning whether one was started
+    ///
+    /// `now_ms` is any millisecond count that goes up steadily, such as the time since boot. It's
+    /// fine for it to wrap around. Notes are only as accurate as how often this is called.
+    ///
+    pub fn tick<T: ToneOutput>(&mut self, now_ms: u32, output: &mut T) -> Result<bool, T::Error> {
+        if let Some((start_ms, length_ms)) = self.current {
+            if now_ms.wrapping_sub(start_ms) < length_ms {
+                return Ok(false);
+            }
+
+            self.current = None;
+            self.position += 1;
+        }
+
+        if self.state != PlayerState::Playing {
+            return Ok(false);
+        }
+
+        if self.position >= self.song.len() {
+            if !self.looping || self.song.is_empty() {
+                self.stop();
+                return Ok(false);
+            }
+
+            self.position = 0;
+        }
+
+        let Some(timing) = self.song.timing(self.position) else {
+            return Ok(false);
+        };
+
+        // Rests are just a wait, the buzzer is already quiet
+        if timing.note != Note::Rest {
+            let dur = timing.sounding_ms.min(u16::MAX as u32) as u16;
+            output.tone(&timing.note, dur)?;
+        }
+
+        self.current = Some((now_ms, timing.length_ms));
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,8 @@
+//! Ringtones from the `assets` folder, converted by build.rs
+//!
+//! Drop RTTTL files into `assets/ringtones` and each one becomes a constant named after its file,
+//! such as `ODE_TO_JOY` for `ode_to_joy.rtttl`.
+
+use super::rtttl::{Ringtone, RtttlNote};
+
+include!(concat!(env!("OUT_DIR"), "/ringtones.rs"));
@@ -0,0 +1,197 @@
+//! Parses ringtones in Nokia's Ring Tone Text Transfer Language (RTTTL)
+//!
+//! A ringtone looks like `tune:d=4,o=5,b=120:e,8f#,2g.,p,c6`, which is a name, the default
+//! duration, octave and tempo, then the notes. This only uses `core`, so build.rs can include it
+//! to convert ringtones at build time.
+
+/// Defaults from the RTTTL specification, for any that a ringtone leaves out
+const DEFAULT_DURATION: u8 = 4;
+const DEFAULT_OCTAVE: u8 = 6;
+const DEFAULT_BPM: u16 = 63;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RtttlError {
+    /// The name, defaults and notes weren't all there, separated by `:`
+    MissingSection,
+    /// A default had a key or value that couldn't be read
+    BadDefault,
+    /// The note at this index couldn't be read
+    BadNote(usize),
+}
+
+///
+/// One note or rest of a ringtone
+///
+/// Converts into a `Note` and a `NoteLength`, which know how long it lasts at a given tempo.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RtttlNote {
+    /// MIDI note number, with middle C as 60, or `None` for a rest
+    pub midi: Option<u8>,
+    /// Fraction of a whole note, such as 4 for a quarter note
+    pub duration: u8,
+    /// Half as long again
+    pub dotted: bool,
+}
+
+impl RtttlNote {
+    pub const fn new(midi: Option<u8>, duration: u8, dotted: bool) -> Self {
+        Self {
+            midi,
+            duration,
+            dotted,
+        }
+    }
+}
+
+/// A ringtone converted from `assets/ringtones` at build time
+pub struct Ringtone {
+    pub name: &'static str,
+    pub bpm: u16,
+    pub notes: &'static [RtttlNote],
+}
+
+/// A ringtone read from text at runtime
+pub struct Rtttl<'a> {
+    pub name: &'a str,
+    /// Used by notes that don't give their own duration
+    pub duration: u8,
+    /// Used by notes that don't give their own octave
+    pub octave: u8,
+    pub bpm: u16,
+    notes: &'a str,
+}
+
+/// Reads the number at the start of `bytes`, moving `index` past it
+fn number(bytes: &[u8], index: &mut usize) -> Option<u32> {
+    let start = *index;
+    let mut value: u32 = 0;
+
+    while let Some(digit) = bytes.get(*index).filter(|byte| byte.is_ascii_digit()) {
+        value = value.checked_mul(10)?.checked_add((digit - b'0') as u32)?;
+        *index += 1;
+    }
+
+    (*index > start).then_some(value)
+}
+
+fn valid_duration(duration: u32) -> Option<u8> {
+    matches!(duration, 1 | 2 | 4 | 8 | 16 | 32).then_some(duration as u8)
+}
+
+/// Reads a note such as `8c#6.`, with anything it leaves out taken from the defaults
+fn parse_note(token: &str, default_duration: u8, default_octave: u8) -> Option<RtttlNote> {
+    let bytes = token.as_bytes();
+    let mut index = 0;
+
+    let duration = match number(bytes, &mut index) {
+        Some(duration) => valid_duration(duration)?,
+        None => default_duration,
+    };
+
+    // Semitones above C, with `h` being the German name for B
+    let semitone = match bytes.get(index)?.to_ascii_lowercase() {
+        b'c' => Some(0),
+        b'd' => Some(2),
+        b'e' => Some(4),
+        b'f' => Some(5),
+        b'g' => Some(7),
+        b'a' => Some(9),
+        b'b' | b'h' => Some(11),
+        b'p' => None,
+        _ => return None,
+    };
+    index += 1;
+
+    let sharp = bytes.get(index) == Some(&b'#');
+    if sharp {
+        index += 1;
+    }
+
+    // Some ringtones put the dot before the octave rather than after it
+    let mut dotted = bytes.get(index) == Some(&b'.');
+    if dotted {
+        index += 1;
+    }
+
+    let octave = match number(bytes, &mut index) {
+        Some(octave) => u8::try_from(octave).ok()?,
+        None => default_octave,
+    };
+
+    if bytes.get(index) == Some(&b'.') {
+        dotted = true;
+        index += 1;
+    }
+
+    if index != bytes.len() {
+        return None;
+    }
+
+    let midi = match semitone {
+        Some(semitone) => {
+            let midi = 12 * (octave as u32 + 1) + semitone + sharp as u32;
+            Some(u8::try_from(midi).ok().filter(|midi| *midi < 128)?)
+        }
+        // A sharp rest doesn't mean anything
+        None if sharp => return None,
+        None => None,
+    };
+
+    Some(RtttlNote::new(midi, duration, dotted))
+}
+
+impl<'a> Rtttl<'a> {
+    /// Reads a ringtone, checking every note up front so that `notes` can't fail
+    pub fn parse(text: &'a str) -> Result<Self, RtttlError> {
+        let mut sections = text.trim().splitn(3, ':');
+
+        let (Some(name), Some(defaults), Some(notes)) =
+            (sections.next(), sections.next(), sections.next())
+        else {
+            return Err(RtttlError::MissingSection);
+        };
+
+        let mut ringtone = Self {
+            name: name.trim(),
+            duration: DEFAULT_DURATION,
+            octave: DEFAULT_OCTAVE,
+            bpm: DEFAULT_BPM,
+            notes,
+        };
+
+        for default in defaults.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let (key, value) = default.split_once('=').ok_or(RtttlError::BadDefault)?;
+            let value: u32 = value.trim().parse().map_err(|_| RtttlError::BadDefault)?;
+
+            match key.trim() {
+                "d" => ringtone.duration = valid_duration(value).ok_or(RtttlError::BadDefault)?,
+                "o" if value <= 9 => ringtone.octave = value as u8,
+                "b" if (1..=u16::MAX as u32).contains(&value) => ringtone.bpm = value as u16,
+                _ => return Err(RtttlError::BadDefault),
+            }
+        }
+
+        for (index, token) in ringtone.tokens().enumerate() {
+            if parse_note(token, ringtone.duration, ringtone.octave).is_none() {
+                return Err(RtttlError::BadNote(index));
+            }
+        }
+
+        Ok(ringtone)
+    }
+
+    fn tokens(&self) -> impl Iterator<Item = &'a str> {
+        self.notes
+            .split(',')
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+    }
+
+    pub fn notes(&self) -> impl Iterator<Item = RtttlNote> + 'a {
+        let (duration, octave) = (self.duration, self.octave);
+
+        self.tokens()
+            .filter_map(move |token| parse_note(token, duration, octave))
+    }
+}
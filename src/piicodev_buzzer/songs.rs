@@ -0,0 +1,9 @@
+//! Songs from the `assets` folder, converted by build.rs
+//!
+//! Drop MIDI files into `assets/songs` and each one becomes a constant named after its file, such
+//! as `FRERE_JACQUES` for `frere_jacques.mid`. The buzzer can only play one note at a time, so
+//! the highest note is kept wherever they overlap.
+
+use super::notes::{Note, NoteLength, Song};
+
+include!(concat!(env!("OUT_DIR"), "/songs.rs"));
@@ -0,0 +1,18 @@
+use rp_pico::hal::i2c::Error;
+
+/// Errors shared by the PiicoDev drivers that do more than pass I2C errors through
+#[derive(Debug)]
+pub enum PiicoDevError {
+    I2C(Error),
+    /// Something answered at the address, but it isn't the expected device
+    UnexpectedDeviceId {
+        expected: u8,
+        found: u8,
+    },
+}
+
+impl From<Error> for PiicoDevError {
+    fn from(error: Error) -> Self {
+        Self::I2C(error)
+    }
+}
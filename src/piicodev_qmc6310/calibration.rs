@@ -0,0 +1,305 @@
+/// Identifies a calibration record, so blank or foreign flash isn't mistaken for one
+const MAGIC: [u8; 4] = *b"QMC6";
+
+/// Bumped whenever the record layout changes
+pub const CALIBRATION_VERSION: u8 = 1;
+
+/// magic + version + six f32 extremes + three u16 offsets + crc32
+pub const RECORD_SIZE: usize = 4 + 1 + 6 * 4 + 3 * 2 + 4;
+
+/// Where the checksum starts. Everything before it is covered by it.
+const CHECKSUM_INDEX: usize = RECORD_SIZE - 4;
+
+/// The result of rotating the magnetometer through every axis
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Calibration {
+    pub x_min: f32,
+    pub x_max: f32,
+    pub y_min: f32,
+    pub y_max: f32,
+    pub z_min: f32,
+    pub z_max: f32,
+    pub x_offset: u16,
+    pub y_offset: u16,
+    pub z_offset: u16,
+}
+
+/// Why a stored calibration record couldn't be used
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CalibrationError {
+    /// Nothing that looks like a calibration record has been stored
+    NotFound,
+    /// The record was written by an incompatible version of this driver
+    UnsupportedVersion(u8),
+    /// The record is corrupt
+    InvalidChecksum,
+}
+
+///
+/// Somewhere a serialised calibration record can be kept between reboots
+///
+/// Implemented for a flash sector on the device, and for a plain array which keeps the record in
+/// RAM, such as on the host to exercise the format.
+///
+pub trait CalibrationStorage {
+    /// Reads the stored record. Storage that has never been written may contain anything.
+    fn read_record(&mut self, record: &mut [u8; RECORD_SIZE]);
+
+    /// Replaces the stored record
+    fn write_record(&mut self, record: &[u8; RECORD_SIZE]);
+}
+
+impl CalibrationStorage for [u8; RECORD_SIZE] {
+    fn read_record(&mut self, record: &mut [u8; RECORD_SIZE]) {
+        record.copy_from_slice(self);
+    }
+
+    fn write_record(&mut self, record: &[u8; RECORD_SIZE]) {
+        self.copy_from_slice(record);
+    }
+}
+
+/// CRC-32 (IEEE 802.3), computed bit by bit to avoid a lookup table
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF;
+
+    for byte in bytes {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+impl Calibration {
+    /// Serialises the calibration into a versioned, checksummed record
+    pub fn encode(&self) -> [u8; RECORD_SIZE] {
+        let mut record = [0; RECORD_SIZE];
+
+        record[0..4].copy_from_slice(&MAGIC);
+        record[4] = CALIBRATION_VERSION;
+
+        let extremes = [
+            self.x_min, self.x_max, self.y_min, self.y_max, self.z_min, self.z_max,
+        ];
+
+        for (i, value) in extremes.iter().enumerate() {
+            let start = 5 + i * 4;
+            record[start..start + 4].copy_from_slice(&value.to_le_bytes());
+        }
+
+        let offsets = [self.x_offset, self.y_offset, self.z_offset];
+
+        for (i, value) in offsets.iter().enumerate() {
+            let start = 29 + i * 2;
+            record[start..start + 2].copy_from_slice(&value.to_le_bytes());
+        }
+
+        let checksum = crc32(&record[..CHECKSUM_INDEX]);
+        record[CHECKSUM_INDEX..].copy_from_slice(&checksum.to_le_bytes());
+
+        record
+    }
+
+    /// Parses a record created by `encode`
+    pub fn decode(record: &[u8; RECORD_SIZE]) -> Result<Self, CalibrationError> {
+        if record[0..4] != MAGIC {
+            return Err(CalibrationError::NotFound);
+        }
+
+        if record[4] != CALIBRATION_VERSION {
+            return Err(CalibrationError::UnsupportedVersion(record[4]));
+        }
+
+        let checksum = u32::from_le_bytes([
+            record[CHECKSUM_INDEX],
+            record[CHECKSUM_INDEX + 1],
+            record[CHECKSUM_INDEX + 2],
+            record[CHECKSUM_INDEX + 3],
+        ]);
+
+        if checksum != crc32(&record[..CHECKSUM_INDEX]) {
+            return Err(CalibrationError::InvalidChecksum);
+        }
+
+        let f32_at = |start: usize| {
+            f32::from_le_bytes([
+                record[start],
+                record[start + 1],
+                record[start + 2],
+                record[start + 3],
+            ])
+        };
+
+        let u16_at = |start: usize| u16::from_le_bytes([record[start], record[start + 1]]);
+
+        Ok(Self {
+            x_min: f32_at(5),
+            x_max: f32_at(9),
+            y_min: f32_at(13),
+            y_max: f32_at(17),
+            z_min: f32_at(21),
+            z_max: f32_at(25),
+            x_offset: u16_at(29),
+            y_offset: u16_at(31),
+            z_offset: u16_at(33),
+        })
+    }
+
+    /// Loads and validates the record held by `storage`
+    pub fn load<S: CalibrationStorage>(storage: &mut S) -> Result<Self, CalibrationError> {
+        let mut record = [0; RECORD_SIZE];
+        storage.read_record(&mut record);
+
+        Self::decode(&record)
+    }
+
+    /// Overwrites whatever `storage` held with this calibration
+    pub fn save<S: CalibrationStorage>(&self, storage: &mut S) {
+        storage.write_record(&self.encode());
+    }
+}
+
+/// How many samples in a row must fail to extend any axis before calibration is complete
+pub const CALIBRATION_SAMPLES: u16 = 1000;
+
+/// Weight given to each new sample by the EMA filter
+const FILTER_WEIGHT: f32 = 0.5;
+
+/// What happened while calibrating, for reporting progress however the caller likes
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CalibrationEvent {
+    /// A raw sample has been filtered and checked against the extremes seen so far
+    Sample {
+        /// Samples in a row without a new extreme. Resets to 0 whenever one is found.
+        progress: u16,
+        /// Value of `progress` that completes calibration
+        total: u16,
+        x: f32,
+        y: f32,
+        z: f32,
+    },
+    /// Calibration is complete
+    Finished(Calibration),
+}
+
+///
+/// Tracks the extremes of each axis as the sensor is rotated
+///
+/// Fed raw samples one at a time and reports back through `CalibrationEvent`s, so it is
+/// independent of both the I2C bus and however progress gets displayed.
+///
+pub struct Calibrator {
+    x: f32,
+    y: f32,
+    z: f32,
+    x_min: f32,
+    x_max: f32,
+    y_min: f32,
+    y_max: f32,
+    z_min: f32,
+    z_max: f32,
+    progress: u16,
+}
+
+impl Calibrator {
+    pub fn new() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            x_min: 65535.0,
+            x_max: -65535.0,
+            y_min: 65535.0,
+            y_max: -65535.0,
+            z_min: 65535.0,
+            z_max: -65535.0,
+            progress: 0,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.progress >= CALIBRATION_SAMPLES
+    }
+
+    /// Takes in a raw sample, returning `Finished` once the extremes have stopped moving
+    pub fn update(&mut self, x: f32, y: f32, z: f32) -> CalibrationEvent {
+        if self.is_finished() {
+            return CalibrationEvent::Finished(self.calibration());
+        }
+
+        self.progress += 1;
+
+        let a = FILTER_WEIGHT;
+        self.x = a * x + (1.0 - a) * self.x;
+        self.y = a * y + (1.0 - a) * self.y;
+        self.z = a * z + (1.0 - a) * self.z;
+
+        if self.x < self.x_min {
+            self.x_min = self.x;
+            self.progress = 0;
+        }
+
+        if self.x > self.x_max {
+            self.x_max = self.x;
+            self.progress = 0;
+        }
+
+        if self.y < self.y_min {
+            self.y_min = self.y;
+            self.progress = 0;
+        }
+
+        if self.y > self.y_max {
+            self.y_max = self.y;
+            self.progress = 0;
+        }
+
+        if self.z < self.z_min {
+            self.z_min = self.z;
+            self.progress = 0;
+        }
+
+        if self.z > self.z_max {
+            self.z_max = self.z;
+            self.progress = 0;
+        }
+
+        if self.is_finished() {
+            return CalibrationEvent::Finished(self.calibration());
+        }
+
+        CalibrationEvent::Sample {
+            progress: self.progress,
+            total: CALIBRATION_SAMPLES,
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+
+    /// The calibration given by the extremes seen so far
+    pub fn calibration(&self) -> Calibration {
+        Calibration {
+            x_min: self.x_min,
+            x_max: self.x_max,
+            y_min: self.y_min,
+            y_max: self.y_max,
+            z_min: self.z_min,
+            z_max: self.z_max,
+            x_offset: (self.x_max + self.x_min) as u16 / 2,
+            y_offset: (self.y_max + self.y_min) as u16 / 2,
+            z_offset: (self.z_max + self.z_min) as u16 / 2,
+        }
+    }
+}
+
+impl Default for Calibrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -9,7 +9,10 @@ pub const ADDRESS_CONTROL1: u8 = 0x0A;
 pub const ADDRESS_CONTROL2: u8 = 0x0B;
 pub const ADDRESS_SIGN: u8 = 0x29;
 pub const BIT_MODE: u8 = 0;
+pub const BIT_SET_RESET: u8 = 0;
 pub const BIT_ODR: u8 = 2;
 pub const BIT_OSR1: u8 = 4;
 pub const BIT_OSR2: u8 = 6;
 pub const BIT_RANGE: u8 = 2;
+pub const BIT_SELF_TEST: u8 = 6;
+pub const BIT_SOFT_RESET: u8 = 7;
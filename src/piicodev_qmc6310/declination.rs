@@ -0,0 +1,177 @@
+//! Magnetic declination from the World Magnetic Model
+//!
+//! Evaluates WMM2025, truncated to degree 8 to keep the coefficient table small. The dropped
+//! terms are a few nT each, which moves the declination by well under a degree anywhere people
+//! are likely to be using a compass. The model is valid from 2025 to 2030.
+
+use core::f32::consts::PI;
+
+use libm::{atan2f, cosf, sinf, sqrtf};
+
+/// Year the coefficients were fitted to
+const EPOCH: f32 = 2025.0;
+
+/// Highest spherical harmonic degree in the table
+const MAX_DEGREE: usize = 8;
+
+/// Geomagnetic reference radius in km
+const REFERENCE_RADIUS: f32 = 6371.2;
+
+/// WGS84 semi-major axis in km and first eccentricity squared
+const WGS84_A: f32 = 6378.137;
+const WGS84_E2: f32 = 0.006_694_38;
+
+/// Gauss coefficients (g, h) in nT and their secular variation (g_dot, h_dot) in nT/year, for
+/// each degree n = 1..=MAX_DEGREE and order m = 0..=n
+const COEFFICIENTS: [(f32, f32, f32, f32); 44] = [
+    // Degree 1
+    (-29351.8, 0.0, 12.0, 0.0),
+    (-1410.8, 4545.4, 9.7, -21.5),
+    // Degree 2
+    (-2556.6, 0.0, -11.6, 0.0),
+    (2951.1, -3133.6, -5.2, -27.7),
+    (1649.3, -815.1, -8.0, -12.1),
+    // Degree 3
+    (1361.0, 0.0, -1.3, 0.0),
+    (-2404.1, -56.6, -4.2, 4.0),
+    (1243.8, 237.5, 0.4, -0.3),
+    (453.6, -549.5, -15.6, -4.1),
+    // Degree 4
+    (895.0, 0.0, -1.6, 0.0),
+    (799.5, 278.6, -2.4, -1.1),
+    (55.7, -133.9, -6.0, 4.1),
+    (-281.1, 212.0, 5.6, 1.6),
+    (12.1, -375.6, -7.0, -4.4),
+    // Degree 5
+    (-233.2, 0.0, 0.6, 0.0),
+    (368.9, 45.4, 1.4, -0.5),
+    (187.2, 220.2, 0.0, 2.2),
+    (-138.7, -122.9, 0.6, 0.4),
+    (-142.0, 43.0, 2.2, 1.7),
+    (20.9, 106.1, 0.9, 1.9),
+    // Degree 6
+    (64.4, 0.0, -0.2, 0.0),
+    (63.8, -18.4, -0.4, 0.3),
+    (76.9, 16.8, 0.9, -1.6),
+    (-115.7, 48.8, 1.2, -0.4),
+    (-40.9, -59.8, -0.9, 0.9),
+    (14.9, 10.9, 0.3, 0.7),
+    (-60.7, 72.7, 0.9, 0.9),
+    // Degree 7
+    (79.5, 0.0, 0.0, 0.0),
+    (-77.0, -48.9, -0.1, 0.6),
+    (-8.8, -14.4, -0.1, 0.5),
+    (59.3, -1.0, 0.5, -0.8),
+    (15.8, 23.4, -0.1, 0.0),
+    (2.5, -7.4, -0.8, -1.0),
+    (-11.1, -25.1, -0.8, 0.6),
+    (14.2, -2.3, 0.8, -0.2),
+    // Degree 8
+    (23.2, 0.0, -0.1, 0.0),
+    (10.8, 7.1, 0.2, -0.2),
+    (-17.5, -12.6, 0.0, 0.5),
+    (2.0, 11.4, 0.5, -0.4),
+    (-21.7, -9.7, -0.1, 0.4),
+    (16.9, 12.7, 0.3, -0.5),
+    (15.0, 0.7, 0.2, -0.6),
+    (-16.8, -5.2, 0.0, 0.3),
+    (0.9, 3.9, 0.2, 0.2),
+];
+
+///
+/// Angle between true north and magnetic north in degrees, positive when magnetic north is east
+/// of true north
+///
+/// `latitude` and `longitude` are in degrees (south and west negative), and `year` is a decimal
+/// year such as 2025.5 for the middle of 2025. Assumes sea level.
+///
+pub fn declination(latitude: f32, longitude: f32, year: f32) -> f32 {
+    let phi = latitude * PI / 180.0;
+    let lambda = longitude * PI / 180.0;
+
+    // Convert the geodetic position on the WGS84 ellipsoid to geocentric spherical coordinates
+    let sin_phi = sinf(phi);
+    let cos_phi = cosf(phi);
+    let prime_vertical = WGS84_A / sqrtf(1.0 - WGS84_E2 * sin_phi * sin_phi);
+    let p = prime_vertical * cos_phi;
+    let z = prime_vertical * (1.0 - WGS84_E2) * sin_phi;
+    let r = sqrtf(p * p + z * z);
+    let phi_c = atan2f(z, p);
+
+    let sin_phi_c = sinf(phi_c);
+    let cos_phi_c = cosf(phi_c);
+
+    // Schmidt semi-normalised associated Legendre functions of sin(phi_c), and their derivatives
+    // with respect to phi_c
+    let mut legendre = [[0.0f32; MAX_DEGREE + 1]; MAX_DEGREE + 1];
+    let mut legendre_derivative = [[0.0f32; MAX_DEGREE + 1]; MAX_DEGREE + 1];
+    legendre[0][0] = 1.0;
+
+    for n in 1..=MAX_DEGREE {
+        for m in 0..=n {
+            if n == m {
+                let k = if n == 1 {
+                    1.0
+                } else {
+                    sqrtf(1.0 - 1.0 / (2 * n) as f32)
+                };
+
+                legendre[n][m] = k * cos_phi_c * legendre[n - 1][m - 1];
+                legendre_derivative[n][m] = k
+                    * (cos_phi_c * legendre_derivative[n - 1][m - 1]
+                        - sin_phi_c * legendre[n - 1][m - 1]);
+                continue;
+            }
+
+            let nn = (n * n) as f32;
+            let mm = (m * m) as f32;
+            let a = (2 * n - 1) as f32 / sqrtf(nn - mm);
+
+            legendre[n][m] = a * sin_phi_c * legendre[n - 1][m];
+            legendre_derivative[n][m] =
+                a * (sin_phi_c * legendre_derivative[n - 1][m] + cos_phi_c * legendre[n - 1][m]);
+
+            if n >= m + 2 {
+                let b = sqrtf((((n - 1) * (n - 1)) as f32 - mm) / (nn - mm));
+
+                legendre[n][m] -= b * legendre[n - 2][m];
+                legendre_derivative[n][m] -= b * legendre_derivative[n - 2][m];
+            }
+        }
+    }
+
+    // Sum the field components in the geocentric frame
+    let years = year - EPOCH;
+    let mut north = 0.0;
+    let mut east = 0.0;
+    let mut down = 0.0;
+    let mut index = 0;
+
+    for n in 1..=MAX_DEGREE {
+        let radius_ratio = libm::powf(REFERENCE_RADIUS / r, (n + 2) as f32);
+
+        for m in 0..=n {
+            let (g, h, g_dot, h_dot) = COEFFICIENTS[index];
+            index += 1;
+
+            let g = g + g_dot * years;
+            let h = h + h_dot * years;
+
+            let cos_m_lambda = cosf(m as f32 * lambda);
+            let sin_m_lambda = sinf(m as f32 * lambda);
+            let gh = g * cos_m_lambda + h * sin_m_lambda;
+
+            north -= radius_ratio * gh * legendre_derivative[n][m];
+            east +=
+                radius_ratio * m as f32 * (g * sin_m_lambda - h * cos_m_lambda) * legendre[n][m]
+                    / cos_phi_c;
+            down -= (n + 1) as f32 * radius_ratio * gh * legendre[n][m];
+        }
+    }
+
+    // Rotate back onto the ellipsoid. East is unaffected, and declination doesn't need down.
+    let psi = phi_c - phi;
+    let north = north * cosf(psi) - down * sinf(psi);
+
+    atan2f(east, north) * 180.0 / PI
+}
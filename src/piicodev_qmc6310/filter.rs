@@ -0,0 +1,133 @@
+use core::f32::consts::PI;
+
+use libm::{atan2f, cosf, sinf};
+
+/// Most headings the moving average can be taken over
+pub const MAX_WINDOW: usize = 32;
+
+/// Below this length the averaged vector doesn't point anywhere meaningful
+const MIN_RESULTANT: f32 = 1e-6;
+
+/// How consecutive headings are combined
+#[derive(Copy, Clone, PartialEq)]
+pub enum HeadingFilter {
+    /// Every heading is returned as read
+    None,
+    /// Averages the last given number of headings, up to `MAX_WINDOW`
+    MovingAverage(usize),
+    /// Exponential low-pass, where each new heading has the given weight between 0 and 1
+    LowPass(f32),
+}
+
+///
+/// Smooths compass headings
+///
+/// Headings are averaged as unit vectors rather than as numbers, so 359° and 1° average to 0°
+/// instead of 180°.
+///
+pub struct HeadingSmoother {
+    filter: HeadingFilter,
+    samples: [(f32, f32); MAX_WINDOW],
+    count: usize,
+    next: usize,
+    average: Option<(f32, f32)>,
+}
+
+/// Brings an angle within a turn either side of 0° into 0° up to 360°
+pub(super) fn convert_angle_to_positive(angle: f32) -> f32 {
+    if angle >= 360.0 {
+        return angle - 360.0;
+    }
+
+    if angle < 0.0 {
+        let angle = angle + 360.0;
+
+        // Tiny negative angles round up to exactly 360
+        if angle >= 360.0 {
+            return 0.0;
+        }
+
+        return angle;
+    }
+
+    angle
+}
+
+fn to_vector(heading: f32) -> (f32, f32) {
+    let radians = heading * PI / 180.0;
+
+    (cosf(radians), sinf(radians))
+}
+
+fn to_heading((x, y): (f32, f32)) -> f32 {
+    convert_angle_to_positive(atan2f(y, x) * 180.0 / PI)
+}
+
+impl HeadingSmoother {
+    pub fn new(filter: HeadingFilter) -> Self {
+        Self {
+            filter,
+            samples: [(0.0, 0.0); MAX_WINDOW],
+            count: 0,
+            next: 0,
+            average: None,
+        }
+    }
+
+    /// Switches to a different filter, forgetting any previous headings
+    pub fn set_filter(&mut self, filter: HeadingFilter) {
+        self.filter = filter;
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.next = 0;
+        self.average = None;
+    }
+
+    ///
+    /// Adds a heading in degrees and returns the smoothed heading, from 0° up to 360°
+    ///
+    /// Missing readings (NaN) are skipped, returning the previous smoothed heading
+    ///
+    pub fn update(&mut self, heading: f32) -> f32 {
+        if heading.is_nan() {
+            return self.average.map(to_heading).unwrap_or(f32::NAN);
+        }
+
+        let vector = to_vector(heading);
+
+        let average = match self.filter {
+            HeadingFilter::None => vector,
+            HeadingFilter::MovingAverage(window) => {
+                let window = window.clamp(1, MAX_WINDOW);
+
+                self.samples[self.next] = vector;
+                self.next = (self.next + 1) % window;
+                self.count = (self.count + 1).min(window);
+
+                let (x, y) = self.samples[..self.count]
+                    .iter()
+                    .fold((0.0, 0.0), |(x, y), sample| (x + sample.0, y + sample.1));
+
+                (x / self.count as f32, y / self.count as f32)
+            }
+            HeadingFilter::LowPass(alpha) => {
+                let alpha = alpha.clamp(0.0, 1.0);
+                let (x, y) = self.average.unwrap_or(vector);
+
+                (x + alpha * (vector.0 - x), y + alpha * (vector.1 - y))
+            }
+        };
+
+        self.average = Some(average);
+
+        // Opposite headings cancel out, in which case the latest is as good a guess as any
+        if average.0 * average.0 + average.1 * average.1 < MIN_RESULTANT {
+            return heading;
+        }
+
+        to_heading(average)
+    }
+}
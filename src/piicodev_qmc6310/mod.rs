@@ -1,23 +1,26 @@
+pub mod calibration;
 mod constants;
+pub mod declination;
+pub mod filter;
 mod reading;
 
-use core::fmt::Write;
-
 use bsp::hal::i2c::Error;
 use cortex_m::delay::Delay;
+use defmt::warn;
 use embedded_hal::i2c::I2c;
 use rp_pico as bsp;
 
 use crate::{
     i2c::I2CHandler,
     piicodev_qmc6310::constants::{ADDRESS_XOUT, ADDRESS_YOUT, ADDRESS_ZOUT},
-    uart::Uart,
 };
 
+use self::calibration::{Calibration, CalibrationEvent, CalibrationStorage, Calibrator};
 use self::constants::{
-    ADDRESS_CONTROL1, ADDRESS_CONTROL2, ADDRESS_SIGN, ADDRESS_STATUS, BIT_ODR, BIT_OSR1, BIT_OSR2,
-    BIT_RANGE, I2C_ADDRESS,
+    ADDRESS_CONTROL1, ADDRESS_CONTROL2, ADDRESS_SIGN, ADDRESS_STATUS, BIT_MODE, BIT_ODR, BIT_OSR1,
+    BIT_OSR2, BIT_RANGE, BIT_SELF_TEST, BIT_SET_RESET, BIT_SOFT_RESET, I2C_ADDRESS,
 };
+use self::filter::{convert_angle_to_positive, HeadingFilter, HeadingSmoother};
 use self::reading::MagnetometerReading;
 
 #[derive(Copy, Clone)]
@@ -71,6 +74,70 @@ impl Into<f32> for MicroteslaRange {
     }
 }
 
+/// Operating mode, set by control register 1
+#[derive(Copy, Clone, PartialEq)]
+pub enum Mode {
+    /// Lowest power, no measurements are taken
+    Suspend = 0,
+    /// Measures continuously at the output data rate
+    Normal = 1,
+    /// Takes one measurement then returns to suspend
+    Single = 2,
+    /// Measures continuously as fast as the oversampling allows, ignoring the output data rate
+    Continuous = 3,
+}
+
+/// How often measurements are taken in normal mode
+#[derive(Copy, Clone, PartialEq)]
+pub enum OutputDataRate {
+    Hz10 = 0,
+    Hz50 = 1,
+    Hz100 = 2,
+    Hz200 = 3,
+}
+
+/// Over sample ratio (OSR1). Higher ratios reduce noise at the cost of power.
+#[derive(Copy, Clone, PartialEq)]
+pub enum OversampleRatio {
+    Ratio8 = 0,
+    Ratio4 = 1,
+    Ratio2 = 2,
+    Ratio1 = 3,
+}
+
+/// Down sample ratio (OSR2), which further filters each measurement
+#[derive(Copy, Clone, PartialEq)]
+pub enum DownsampleRatio {
+    Ratio1 = 0,
+    Ratio2 = 1,
+    Ratio4 = 2,
+    Ratio8 = 3,
+}
+
+/// How the sensor's set/reset coils are pulsed before measuring, set by control register 2
+#[derive(Copy, Clone, PartialEq)]
+pub enum SetResetMode {
+    SetAndResetOn = 0,
+    SetOnly = 1,
+    SetAndResetOff = 2,
+}
+
+/// Change in each raw axis reading while the self-test field is applied
+pub struct SelfTestResult {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl SelfTestResult {
+    /// Whether every axis responded to the self-test field
+    pub fn passed(&self) -> bool {
+        [self.x, self.y, self.z]
+            .iter()
+            .all(|delta| !delta.is_nan() && *delta != 0.0)
+    }
+}
+
 /// Reads an individual bit from a byte
 fn read_bit(byte: u8, bit_index: u8) -> u8 {
     // Copied from https://users.rust-lang.org/t/extracting-bits-from-bytes/77110/3
@@ -102,18 +169,6 @@ fn write_crumb(x: u8, n: u8, c: u8) -> u8 {
     write_bit(x, n + 1, read_bit(c, 1))
 }
 
-fn convert_angle_to_positive(angle: f32) -> f32 {
-    if angle >= 360.0 {
-        return angle - 360.0;
-    }
-
-    if angle < 0.0 {
-        return angle + 360.0;
-    }
-
-    angle
-}
-
 ///
 /// PiicoDev magnometer
 ///
@@ -121,29 +176,32 @@ fn convert_angle_to_positive(angle: f32) -> f32 {
 ///
 pub struct PiicoDevQMC6310 {
     addr: u8,
-    odr: u8,
-    calibration_file: &'static str,
+    mode: Mode,
+    odr: OutputDataRate,
     suppress_warnings: bool,
     cr1: u8,
     cr2: u8,
-    osr1: u8,
-    osr2: u8,
+    osr1: OversampleRatio,
+    osr2: DownsampleRatio,
+    set_reset: SetResetMode,
     range: GaussRange,
     sensitivity: MicroteslaRange,
     x_offset: u16,
     y_offset: u16,
     z_offset: u16,
     declination: f32,
+    heading_smoother: HeadingSmoother,
     data: [u8; 64], // Meant to be a Python dictionary
 }
 
 impl PiicoDevQMC6310 {
     pub fn new(addr: Option<u8>, range: Option<GaussRange>, declination: f32) -> Self {
         let addr = addr.unwrap_or(I2C_ADDRESS);
-        let odr = 3;
-        let osr1 = 0;
-        let osr2 = 3;
-        let calibration_file = "calibration.cal";
+        let mode = Mode::Normal;
+        let odr = OutputDataRate::Hz200;
+        let osr1 = OversampleRatio::Ratio8;
+        let osr2 = DownsampleRatio::Ratio8;
+        let set_reset = SetResetMode::SetAndResetOn;
         let suppress_warnings = false;
         let cr1 = 0x00;
         let cr2 = 0x00;
@@ -152,65 +210,200 @@ impl PiicoDevQMC6310 {
         let x_offset = 0;
         let y_offset = 0;
         let z_offset = 0;
+        let heading_smoother = HeadingSmoother::new(HeadingFilter::None);
         let data = [0; 64];
 
         Self {
             addr,
+            mode,
             odr,
-            calibration_file,
             suppress_warnings,
             cr1,
             cr2,
             osr1,
             osr2,
+            set_reset,
             range,
             sensitivity,
             x_offset,
             y_offset,
             z_offset,
             declination,
+            heading_smoother,
             data,
         }
     }
 
-    pub fn init(&mut self, i2c: &mut I2CHandler) -> Result<(), Error> {
+    /// Sets the declination in degrees, which is added to every heading to point at true north
+    pub fn set_declination(&mut self, declination: f32) {
+        self.declination = declination;
+    }
+
+    ///
+    /// Works out the declination for a position and decimal year from the World Magnetic Model
+    ///
+    /// Latitude and longitude are in degrees, with south and west negative
+    ///
+    pub fn set_location(&mut self, latitude: f32, longitude: f32, year: f32) {
+        self.declination = declination::declination(latitude, longitude, year);
+    }
+
+    /// Configures the sensor and loads any calibration previously saved to `storage`
+    pub fn init<S: CalibrationStorage>(
+        &mut self,
+        i2c: &mut I2CHandler,
+        storage: &mut S,
+    ) -> Result<(), Error> {
+        self.configure(i2c)?;
+        self.load_calibration(storage);
+
+        Ok(())
+    }
+
+    /// Writes the stored configuration to the sensor
+    fn configure(&mut self, i2c: &mut I2CHandler) -> Result<(), Error> {
         let sign_x = 0;
         let sign_y = 1;
         let sign_z = 1;
         let sign = sign_x + sign_y * 2 + sign_z * 4;
 
-        self.set_mode(1, i2c)?;
+        self.set_mode(self.mode, i2c)?;
         self.set_output_data_rate(self.odr, i2c)?;
-        self.set_oversampling_ratio(self.osr1, i2c)?;
-        self.set_oversampling_rate(self.osr2, i2c)?;
+        self.set_oversample_ratio(self.osr1, i2c)?;
+        self.set_downsample_ratio(self.osr2, i2c)?;
+        self.set_set_reset_mode(self.set_reset, i2c)?;
         self.set_range(self.range, i2c)?;
-        self.set_sign(sign, i2c)?;
-        self.load_calibration();
-
-        Ok(())
+        self.set_sign(sign, i2c)
     }
 
-    fn set_mode(&mut self, mode: u8, i2c: &mut I2CHandler) -> Result<(), Error> {
-        self.cr1 = write_crumb(self.cr1, 0, mode);
+    pub fn set_mode(&mut self, mode: Mode, i2c: &mut I2CHandler) -> Result<(), Error> {
+        self.mode = mode;
+        self.cr1 = write_crumb(self.cr1, BIT_MODE, mode as u8);
         i2c.write(self.addr, &[ADDRESS_CONTROL1, self.cr1])
     }
 
-    fn set_output_data_rate(&mut self, odr: u8, i2c: &mut I2CHandler) -> Result<(), Error> {
-        self.cr1 = write_crumb(self.cr1, BIT_ODR, odr);
+    /// Only used in `Mode::Normal`
+    pub fn set_output_data_rate(
+        &mut self,
+        odr: OutputDataRate,
+        i2c: &mut I2CHandler,
+    ) -> Result<(), Error> {
+        self.odr = odr;
+        self.write_output_data_rate(odr, i2c)
+    }
+
+    /// Changes the rate without remembering it, so it can be restored after calibrating
+    fn write_output_data_rate(
+        &mut self,
+        odr: OutputDataRate,
+        i2c: &mut I2CHandler,
+    ) -> Result<(), Error> {
+        self.cr1 = write_crumb(self.cr1, BIT_ODR, odr as u8);
         i2c.write(self.addr, &[ADDRESS_CONTROL1, self.cr1])
     }
 
-    fn set_oversampling_ratio(&mut self, osr1: u8, i2c: &mut I2CHandler) -> Result<(), Error> {
-        self.cr1 = write_crumb(self.cr1, BIT_OSR1, osr1);
+    pub fn set_oversample_ratio(
+        &mut self,
+        osr1: OversampleRatio,
+        i2c: &mut I2CHandler,
+    ) -> Result<(), Error> {
+        self.osr1 = osr1;
+        self.cr1 = write_crumb(self.cr1, BIT_OSR1, osr1 as u8);
         i2c.write(self.addr, &[ADDRESS_CONTROL1, self.cr1])
     }
 
-    fn set_oversampling_rate(&mut self, osr2: u8, i2c: &mut I2CHandler) -> Result<(), Error> {
-        self.cr1 = write_crumb(self.cr1, BIT_OSR2, osr2);
+    pub fn set_downsample_ratio(
+        &mut self,
+        osr2: DownsampleRatio,
+        i2c: &mut I2CHandler,
+    ) -> Result<(), Error> {
+        self.osr2 = osr2;
+        self.cr1 = write_crumb(self.cr1, BIT_OSR2, osr2 as u8);
         i2c.write(self.addr, &[ADDRESS_CONTROL1, self.cr1])
     }
 
-    fn set_range(&mut self, range: GaussRange, i2c: &mut I2CHandler) -> Result<(), Error> {
+    pub fn set_set_reset_mode(
+        &mut self,
+        set_reset: SetResetMode,
+        i2c: &mut I2CHandler,
+    ) -> Result<(), Error> {
+        self.set_reset = set_reset;
+        self.cr2 = write_crumb(self.cr2, BIT_SET_RESET, set_reset as u8);
+        i2c.write(self.addr, &[ADDRESS_CONTROL2, self.cr2])
+    }
+
+    ///
+    /// Resets every register on the sensor, then writes the current configuration back
+    ///
+    /// Mode, data rate, ratios and range carry on as they were, and the calibration is kept.
+    ///
+    pub fn soft_reset(&mut self, i2c: &mut I2CHandler, delay: &mut Delay) -> Result<(), Error> {
+        i2c.write(self.addr, &[ADDRESS_CONTROL2, set_bit(0, BIT_SOFT_RESET)])?;
+
+        // The registers take a moment to come back after a reset
+        delay.delay_ms(1);
+
+        self.cr1 = 0x00;
+        self.cr2 = 0x00;
+
+        self.configure(i2c)
+    }
+
+    ///
+    /// Applies the internal self-test field and measures how much each axis moves
+    ///
+    /// Runs in continuous mode, restoring the previous mode afterwards
+    ///
+    pub fn self_test(
+        &mut self,
+        i2c: &mut I2CHandler,
+        delay: &mut Delay,
+    ) -> Result<SelfTestResult, Error> {
+        let mode = self.mode;
+        self.set_mode(Mode::Continuous, i2c)?;
+
+        let (x_before, y_before, z_before) = self.read_when_ready(i2c, delay)?;
+
+        i2c.write(
+            self.addr,
+            &[ADDRESS_CONTROL2, set_bit(self.cr2, BIT_SELF_TEST)],
+        )?;
+        delay.delay_ms(5);
+
+        let (x_after, y_after, z_after) = self.read_when_ready(i2c, delay)?;
+
+        i2c.write(self.addr, &[ADDRESS_CONTROL2, self.cr2])?;
+        self.set_mode(mode, i2c)?;
+
+        Ok(SelfTestResult {
+            x: x_after - x_before,
+            y: y_after - y_before,
+            z: z_after - z_before,
+        })
+    }
+
+    /// Waits up to 100ms for a raw sample
+    fn read_when_ready(
+        &mut self,
+        i2c: &mut I2CHandler,
+        delay: &mut Delay,
+    ) -> Result<(f32, f32, f32), Error> {
+        let mut sample = (f32::NAN, f32::NAN, f32::NAN);
+
+        for _ in 0..100 {
+            sample = self.read(true, i2c)?;
+
+            if !sample.0.is_nan() {
+                break;
+            }
+
+            delay.delay_ms(1);
+        }
+
+        Ok(sample)
+    }
+
+    pub fn set_range(&mut self, range: GaussRange, i2c: &mut I2CHandler) -> Result<(), Error> {
         let range_bit = match range {
             GaussRange::Gauss3000 => 0,
             GaussRange::Gauss1200 => 1,
@@ -244,16 +437,15 @@ impl PiicoDevQMC6310 {
         read_bit(status, 1) != 0
     }
 
+    /// Raw samples leave out both the calibration offsets and the sensitivity, like the
+    /// MicroPython module's `read(raw=True)`
     fn read(&mut self, raw: bool, i2c: &mut I2CHandler) -> Result<(f32, f32, f32), Error> {
-        fn calculate_value(raw_value: u16, offset: u16) -> f32 {
-            let mut value = raw_value as f32;
-            let offset = offset as f32;
-
+        fn calculate_value(raw_value: u16) -> f32 {
             if raw_value >= 0x8000 {
-                value = !((65535 - raw_value) + 1) as f32;
+                return !((65535 - raw_value) + 1) as f32;
             }
 
-            value - offset
+            raw_value as f32
         }
 
         const NAN: (f32, f32, f32) = (f32::NAN, f32::NAN, f32::NAN);
@@ -296,16 +488,16 @@ impl PiicoDevQMC6310 {
             return Ok(NAN);
         }
 
-        let x = calculate_value(x_from_buffer, self.x_offset);
-        let y = calculate_value(y_from_buffer, self.y_offset);
-        let z = calculate_value(z_from_buffer, self.z_offset);
+        let x = calculate_value(x_from_buffer);
+        let y = calculate_value(y_from_buffer);
+        let z = calculate_value(z_from_buffer);
 
         let sensitivity: f32 = self.sensitivity.into();
 
         if !raw {
-            let x = x * sensitivity;
-            let y = y * sensitivity;
-            let z = z * sensitivity;
+            let x = (x - self.x_offset as f32) * sensitivity;
+            let y = (y - self.y_offset as f32) * sensitivity;
+            let z = (z - self.z_offset as f32) * sensitivity;
 
             let sample = (x, y, z);
 
@@ -317,18 +509,12 @@ impl PiicoDevQMC6310 {
         Ok(sample)
     }
 
-    pub fn read_polar(
-        &mut self,
-        i2c: &mut I2CHandler,
-        uart: &mut Uart,
-    ) -> Result<MagnetometerReading, Error> {
+    pub fn read_polar(&mut self, i2c: &mut I2CHandler) -> Result<MagnetometerReading, Error> {
         const PI: f32 = 3.14159265358979323846;
 
         let (x, y, z) = self.read(false, i2c)?;
-        // writeln!(uart, "{} {} {}", x, y, z).unwrap();
 
         let angle = (libm::atan2f(x, -y) / PI) * 180.0 + self.declination;
-        // writeln!(uart, "{}", angle).unwrap();
         let angle = convert_angle_to_positive(angle);
 
         let magnitude = libm::sqrtf(x * x + y * y + z * z);
@@ -343,125 +529,85 @@ impl PiicoDevQMC6310 {
         Ok(reading)
     }
 
-    pub fn calibrate(
-        &mut self,
-        enable_logging: bool,
-        i2c: &mut I2CHandler,
-        uart: &mut Uart,
-        delay: &mut Delay,
-    ) -> Result<(), Error> {
-        self.set_output_data_rate(3, i2c)?;
-
-        let mut x_min = 65535.0;
-        let mut x_max = -65535.0;
-        let mut y_min = 65535.0;
-        let mut y_max = -65535.0;
-        let mut z_min = 65535.0;
-        let mut z_max = -65535.0;
-
-        // log = ''
-        writeln!(
-            uart,
-            "*** Calibrating.\n    Slowly rotate your sensor until the bar is full"
-        )
-        .unwrap();
-        write!(uart, "[          ]").unwrap();
-        let range = 1000;
-        let mut i = 0;
-
-        let mut x = 0.0;
-        let mut y = 0.0;
-        let mut z = 0.0;
-
-        // EMA filter weight
-        let a = 0.5;
-
-        while i < range {
-            i += 1;
-            delay.delay_ms(5);
+    /// Heading in degrees from north, smoothed by the heading filter
+    pub fn read_heading(&mut self, i2c: &mut I2CHandler) -> Result<f32, Error> {
+        let reading = self.read_polar(i2c)?;
 
-            let (polar, gauss, magnitude) = self.read(true, i2c)?;
+        Ok(self.heading_smoother.update(reading.polar))
+    }
 
-            x = a * polar + (1.0 - a) * x;
-            y = a * gauss + (1.0 - a) * y;
-            z = a * magnitude + (1.0 - a) * z;
+    /// Strength of the magnetic field in microtesla
+    pub fn read_magnitude(&mut self, i2c: &mut I2CHandler) -> Result<f32, Error> {
+        let reading = self.read_polar(i2c)?;
 
-            if x < x_min {
-                x_min = x;
-                i = 0;
-            }
+        Ok(reading.magnitude)
+    }
 
-            if x > x_max {
-                x_max = x;
-                i = 0;
-            }
+    /// Changes how `read_heading` smooths consecutive headings
+    pub fn set_heading_filter(&mut self, filter: HeadingFilter) {
+        self.heading_smoother.set_filter(filter);
+    }
 
-            if y < y_min {
-                y_min = y;
-                i = 0;
-            }
+    ///
+    /// Finds the hard-iron offsets of each axis and saves them to `storage`
+    ///
+    /// The sensor needs to be slowly rotated through every orientation until calibration
+    /// finishes. Every sample is passed to `on_progress`, so the caller can show a progress bar on
+    /// whatever it likes.
+    ///
+    pub fn calibrate<S, F>(
+        &mut self,
+        i2c: &mut I2CHandler,
+        delay: &mut Delay,
+        storage: &mut S,
+        mut on_progress: F,
+    ) -> Result<Calibration, Error>
+    where
+        S: CalibrationStorage,
+        F: FnMut(CalibrationEvent),
+    {
+        self.write_output_data_rate(OutputDataRate::Hz200, i2c)?;
+
+        let mut calibrator = Calibrator::new();
+
+        let calibration = loop {
+            delay.delay_ms(5);
 
-            if y > y_max {
-                y_max = y;
-                i = 0;
-            }
+            let (x, y, z) = self.read(true, i2c)?;
+            let event = calibrator.update(x, y, z);
 
-            if z < z_min {
-                z_min = z;
-                i = 0;
-            }
+            on_progress(event);
 
-            if z > z_max {
-                z_max = z;
-                i = 0;
+            if let CalibrationEvent::Finished(calibration) = event {
+                break calibration;
             }
+        };
 
-            let j = 10 * i / range;
-
-            write!(uart, "\015[").unwrap();
+        // set the output data rate back to the user selected rate
+        self.write_output_data_rate(self.odr, i2c)?;
 
-            for _ in 0..j {
-                write!(uart, "*").unwrap();
-            }
+        self.apply_calibration(&calibration);
+        calibration.save(storage);
 
-            for _ in 0..10 - j {
-                write!(uart, " ").unwrap();
-            }
+        Ok(calibration)
+    }
 
-            writeln!(uart, "]").unwrap();
+    fn apply_calibration(&mut self, calibration: &Calibration) {
+        self.x_offset = calibration.x_offset;
+        self.y_offset = calibration.y_offset;
+        self.z_offset = calibration.z_offset;
+    }
 
-            if enable_logging {
-                let _ = writeln!(uart, "x: {}, y: {}, z: {}", x, y, z);
-            }
+    fn load_calibration<S: CalibrationStorage>(&mut self, storage: &mut S) {
+        if let Ok(calibration) = Calibration::load(storage) {
+            self.apply_calibration(&calibration);
+            return;
         }
 
-        // set the output data rate back to the user selected rate
-        self.set_output_data_rate(self.odr, i2c)?;
-
-        let x_offset = (x_max + x_min) as u16 / 2;
-        let y_offset = (y_max + y_min) as u16 / 2;
-        let z_offset = (z_max + z_min) as u16 / 2;
-
-        write!(
-            uart,
-            "x_min:\n{}\nx_max:\n{}\ny_min:\n{}\ny_max:\n{}\nz_min:\n{}\nz_max:\n{}\nx_offset:\n{}\ny_offset:\n{}\nz_offset:\n{}",
-            x_min, x_max, y_min, y_max, z_min, z_max, x_offset, y_offset, z_offset
-        )
-        .unwrap();
-
-        // f = open(self.calibrationFile, "w")
-        // f.write('x_min:\n' + str(x_min) + '\nx_max:\n' + str(x_max) + '\ny_min:\n' + str(y_min) + '\ny_max:\n' + str(y_max) + '\nz_min\n' + str(z_min) + '\nz_max:\n' + str(z_max) + '\nx_offset:\n')
-        // f.write(str(self.x_offset) + '\ny_offset:\n' + str(self.y_offset) + '\nz_offset:\n' + str(self.z_offset))
-        // f.close()
-        // if enable_logging:
-        //     flog = open("calibration.log", "w")
-        //     flog.write(log)
-        //     flog.close
-
-        Ok(())
-    }
+        if !self.suppress_warnings {
+            warn!("No calibration found. Run calibrate() for best results. Visit https://piico.dev/p15 for more info.");
+        }
 
-    fn load_calibration(&mut self) {
         // Harcoded from a previous test
         // let x_min = 0.9501879;
         // let x_max = 65525.46;
@@ -478,28 +624,3 @@ impl PiicoDevQMC6310 {
         self.z_offset = Z_OFFSET;
     }
 }
-
-//     def readMagnitude(self):
-//         return self.readPolar()['uT']
-//
-//     def readHeading(self):
-//         return self.readPolar()['polar']
-//
-
-//
-
-//
-//     def loadCalibration(self):
-//         try:
-//             f = open(self.calibrationFile, "r")
-//             for i in range(13): f.readline()
-//             self.x_offset = float(f.readline())
-//             f.readline()
-//             self.y_offset = float(f.readline())
-//             f.readline()
-//             self.z_offset = float(f.readline())
-//             sleep_ms(5)
-//         except:
-//             if not self.suppress_warnings:
-//                 print("No calibration file found. Run 'calibrate()' for best results.  Visit https://piico.dev/p15 for more info.")
-//             sleep_ms(1000)
@@ -0,0 +1,122 @@
+use super::colour::Colour;
+
+/// Most pixels an `Animator` can drive
+pub const MAX_PIXELS: usize = 64;
+
+/// A row of RGB LEDs that animations can be played on
+pub trait PixelDisplay {
+    type Error;
+
+    fn pixel_count(&self) -> usize;
+
+    /// Changes a pixel, without sending anything to the LEDs
+    fn set_colour(&mut self, index: usize, colour: Colour);
+
+    /// Sends every pixel to the LEDs
+    fn show(&mut self) -> Result<(), Self::Error>;
+}
+
+///
+/// Something that decides the colour of each pixel over time
+///
+/// Effects only work out colours, so the same one can be played on any `PixelDisplay`.
+///
+pub trait Effect {
+    /// Called once per frame, before any colours are asked for
+    fn update(&mut self, _elapsed_ms: u32) {}
+
+    /// The colour of pixel `index` out of `count`, `elapsed_ms` after the effect started
+    fn colour(&mut self, elapsed_ms: u32, index: usize, count: usize) -> Colour;
+}
+
+impl<E: Effect + ?Sized> Effect for &mut E {
+    fn update(&mut self, elapsed_ms: u32) {
+        (**self).update(elapsed_ms)
+    }
+
+    fn colour(&mut self, elapsed_ms: u32, index: usize, count: usize) -> Colour {
+        (**self).colour(elapsed_ms, index, count)
+    }
+}
+
+///
+/// Plays an effect without blocking
+///
+/// Call `tick` as often as the main loop allows. The display is only sent new colours when at
+/// least one pixel has changed, keeping the I2C bus free for everything else.
+///
+pub struct Animator<E: Effect> {
+    effect: E,
+    start_ms: Option<u32>,
+    frame: [Colour; MAX_PIXELS],
+    /// The display hasn't been shown the current frame yet
+    stale: bool,
+}
+
+impl<E: Effect> Animator<E> {
+    pub fn new(effect: E) -> Self {
+        Self {
+            effect,
+            start_ms: None,
+            frame: [Colour::BLACK; MAX_PIXELS],
+            stale: true,
+        }
+    }
+
+    pub fn effect(&mut self) -> &mut E {
+        &mut self.effect
+    }
+
+    /// Swaps in a different effect, starting it from the beginning on the next tick
+    pub fn set_effect(&mut self, effect: E) {
+        self.effect = effect;
+        self.restart();
+    }
+
+    /// Starts the effect from the beginning on the next tick
+    pub fn restart(&mut self) {
+        self.start_ms = None;
+        self.stale = true;
+    }
+
+    ///
+    /// Works out the current frame and shows it if anything changed, returning whether it did
+    ///
+    /// `now_ms` is any millisecond count that goes up steadily, such as the time since boot. It's
+    /// fine for it to wrap around.
+    ///
+    pub fn tick<D: PixelDisplay>(
+        &mut self,
+        now_ms: u32,
+        display: &mut D,
+    ) -> Result<bool, D::Error> {
+        let start_ms = *self.start_ms.get_or_insert(now_ms);
+        let elapsed_ms = now_ms.wrapping_sub(start_ms);
+        let count = display.pixel_count().min(MAX_PIXELS);
+
+        self.effect.update(elapsed_ms);
+
+        let mut changed = false;
+
+        for index in 0..count {
+            let colour = self.effect.colour(elapsed_ms, index, count);
+
+            if self.stale || colour != self.frame[index] {
+                self.frame[index] = colour;
+                display.set_colour(index, colour);
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return Ok(false);
+        }
+
+        // If it doesn't make it, every pixel is sent again next tick
+        self.stale = true;
+        display.show()?;
+        self.stale = false;
+
+        Ok(true)
+    }
+}
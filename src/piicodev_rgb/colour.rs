@@ -0,0 +1,246 @@
+use libm::{fmodf, logf, powf, roundf};
+
+use super::piicodev_rgb::RGB;
+
+/// Gamma of the eye's response to brightness, which the LEDs don't account for
+const GAMMA: f32 = 2.2;
+
+/// An LED colour, with conversions to and from other colour spaces
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// Common white points, for use with `Colour::from_temperature`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ColourTemperature {
+    Candle,
+    Incandescent,
+    Halogen,
+    Fluorescent,
+    Daylight,
+    Overcast,
+    BlueSky,
+}
+
+impl ColourTemperature {
+    pub fn kelvin(&self) -> u16 {
+        match self {
+            Self::Candle => 1900,
+            Self::Incandescent => 2700,
+            Self::Halogen => 3200,
+            Self::Fluorescent => 4200,
+            Self::Daylight => 5500,
+            Self::Overcast => 7000,
+            Self::BlueSky => 10000,
+        }
+    }
+}
+
+/// Rounds a value from 0 to 1 into a channel
+fn to_channel(value: f32) -> u8 {
+    roundf(value.clamp(0.0, 1.0) * 255.0) as u8
+}
+
+/// Wraps `value` into 0 up to `modulus`, where `%` would leave negative values negative
+fn wrap(value: f32, modulus: f32) -> f32 {
+    let remainder = fmodf(value, modulus);
+
+    if remainder < 0.0 {
+        remainder + modulus
+    } else {
+        remainder
+    }
+}
+
+/// Splits a hue into the red, green and blue it's made of, each from 0 to 1
+fn hue_to_rgb(hue: f32, chroma: f32, lightness: f32) -> (f32, f32, f32) {
+    let hue = wrap(hue, 360.0) / 60.0;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match hue as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    (r + lightness, g + lightness, b + lightness)
+}
+
+impl Colour {
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+    pub const RED: Self = Self::new(255, 0, 0);
+    pub const GREEN: Self = Self::new(0, 255, 0);
+    pub const BLUE: Self = Self::new(0, 0, 255);
+    pub const YELLOW: Self = Self::new(255, 255, 0);
+    pub const CYAN: Self = Self::new(0, 255, 255);
+    pub const MAGENTA: Self = Self::new(255, 0, 255);
+    pub const ORANGE: Self = Self::new(255, 128, 0);
+    pub const PURPLE: Self = Self::new(128, 0, 255);
+    pub const PINK: Self = Self::new(255, 64, 128);
+    pub const TEAL: Self = Self::new(0, 128, 128);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    ///
+    /// Converts from hue, saturation and value
+    ///
+    /// Hue is in degrees around the colour wheel, with red at 0°. Saturation and value are from
+    /// 0 to 1.
+    ///
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let value = value.clamp(0.0, 1.0);
+        let chroma = value * saturation;
+
+        let (r, g, b) = hue_to_rgb(hue, chroma, value - chroma);
+
+        Self::new(to_channel(r), to_channel(g), to_channel(b))
+    }
+
+    /// Converts from hue, saturation and lightness, with the same ranges as `from_hsv`
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+
+        let (r, g, b) = hue_to_rgb(hue, chroma, lightness - chroma / 2.0);
+
+        Self::new(to_channel(r), to_channel(g), to_channel(b))
+    }
+
+    /// Channels from 0 to 1, along with the largest, smallest and the hue in degrees
+    fn hue(&self) -> (f32, f32, f32) {
+        let r = self.r as f32 / 255.0;
+        let g = self.g as f32 / 255.0;
+        let b = self.b as f32 / 255.0;
+
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+
+        let hue = if chroma == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * wrap((g - b) / chroma, 6.0)
+        } else if max == g {
+            60.0 * ((b - r) / chroma + 2.0)
+        } else {
+            60.0 * ((r - g) / chroma + 4.0)
+        };
+
+        (hue, max, min)
+    }
+
+    /// Hue in degrees, saturation and value from 0 to 1
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let (hue, max, min) = self.hue();
+        let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+
+        (hue, saturation, max)
+    }
+
+    /// Hue in degrees, saturation and lightness from 0 to 1
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (hue, max, min) = self.hue();
+        let lightness = (max + min) / 2.0;
+
+        let saturation = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+
+        (hue, saturation, lightness)
+    }
+
+    ///
+    /// The colour of light given off by something glowing at `kelvin`
+    ///
+    /// Uses Tanner Helland's curve fit, which is good from 1000K to 40000K.
+    ///
+    pub fn from_temperature(kelvin: u16) -> Self {
+        let temperature = kelvin.clamp(1000, 40000) as f32 / 100.0;
+
+        let r = if temperature <= 66.0 {
+            255.0
+        } else {
+            329.69873 * powf(temperature - 60.0, -0.13320476)
+        };
+
+        let g = if temperature <= 66.0 {
+            99.4708 * logf(temperature) - 161.11957
+        } else {
+            288.12216 * powf(temperature - 60.0, -0.075514846)
+        };
+
+        let b = if temperature >= 66.0 {
+            255.0
+        } else if temperature <= 19.0 {
+            0.0
+        } else {
+            138.51773 * logf(temperature - 10.0) - 305.0448
+        };
+
+        Self::new(
+            to_channel(r / 255.0),
+            to_channel(g / 255.0),
+            to_channel(b / 255.0),
+        )
+    }
+
+    ///
+    /// Adjusts for the eye's non-linear response, so fades and gradients look even
+    ///
+    /// Apply this last, just before a colour is sent to the LEDs.
+    ///
+    pub fn gamma_corrected(&self) -> Self {
+        let correct = |channel: u8| to_channel(powf(channel as f32 / 255.0, GAMMA));
+
+        Self::new(correct(self.r), correct(self.g), correct(self.b))
+    }
+
+    /// Blends towards `other`, where `amount` 0 is this colour and 1 is `other`
+    pub fn lerp(&self, other: Self, amount: f32) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+        let blend =
+            |from: u8, to: u8| roundf(from as f32 + (to as f32 - from as f32) * amount) as u8;
+
+        Self::new(
+            blend(self.r, other.r),
+            blend(self.g, other.g),
+            blend(self.b, other.b),
+        )
+    }
+
+    /// Dims the colour by `factor`, from 0 for off to 1 for unchanged
+    pub fn scale(&self, factor: f32) -> Self {
+        Self::BLACK.lerp(*self, factor)
+    }
+}
+
+impl From<RGB> for Colour {
+    fn from((r, g, b): RGB) -> Self {
+        Self::new(r, g, b)
+    }
+}
+
+impl From<Colour> for RGB {
+    fn from(colour: Colour) -> Self {
+        (colour.r, colour.g, colour.b)
+    }
+}
+
+impl From<ColourTemperature> for Colour {
+    fn from(temperature: ColourTemperature) -> Self {
+        Self::from_temperature(temperature.kelvin())
+    }
+}
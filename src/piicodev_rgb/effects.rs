@@ -0,0 +1,183 @@
+//! Ready made effects for the `Animator`
+
+use core::f32::consts::PI;
+
+use libm::cosf;
+
+use super::animation::Effect;
+use super::colour::Colour;
+
+/// Fades a colour in and out, like a sleeping laptop
+pub struct Breathing {
+    pub colour: Colour,
+    /// Time for one full breath in and out
+    pub period_ms: u32,
+}
+
+impl Breathing {
+    pub fn new(colour: Colour, period_ms: u32) -> Self {
+        Self { colour, period_ms }
+    }
+}
+
+impl Effect for Breathing {
+    fn colour(&mut self, elapsed_ms: u32, _index: usize, _count: usize) -> Colour {
+        let phase = (elapsed_ms % self.period_ms.max(1)) as f32 / self.period_ms.max(1) as f32;
+        let level = (1.0 - cosf(2.0 * PI * phase)) / 2.0;
+
+        // Squared, as the eye is much more sensitive to changes when the LEDs are dim
+        self.colour.scale(level * level)
+    }
+}
+
+/// Cycles through every hue, spread out along the pixels
+pub struct Rainbow {
+    /// Time to go all the way around the colour wheel
+    pub period_ms: u32,
+    /// Hue difference between neighbouring pixels, in degrees
+    pub spread: f32,
+}
+
+impl Rainbow {
+    pub fn new(period_ms: u32, spread: f32) -> Self {
+        Self { period_ms, spread }
+    }
+}
+
+impl Effect for Rainbow {
+    fn colour(&mut self, elapsed_ms: u32, index: usize, _count: usize) -> Colour {
+        let phase = (elapsed_ms % self.period_ms.max(1)) as f32 / self.period_ms.max(1) as f32;
+
+        Colour::from_hsv(phase * 360.0 + index as f32 * self.spread, 1.0, 1.0)
+    }
+}
+
+/// Lit pixels running along the row, like theatre marquee lights
+pub struct Chase {
+    pub colour: Colour,
+    pub background: Colour,
+    /// Time before moving along by one pixel
+    pub step_ms: u32,
+    /// Every this many pixels is lit
+    pub spacing: usize,
+}
+
+impl Chase {
+    pub fn new(colour: Colour, background: Colour, step_ms: u32, spacing: usize) -> Self {
+        Self {
+            colour,
+            background,
+            step_ms,
+            spacing,
+        }
+    }
+}
+
+impl Effect for Chase {
+    fn colour(&mut self, elapsed_ms: u32, index: usize, _count: usize) -> Colour {
+        let step = (elapsed_ms / self.step_ms.max(1)) as usize;
+        let spacing = self.spacing.max(1);
+
+        if index % spacing == step % spacing {
+            self.colour
+        } else {
+            self.background
+        }
+    }
+}
+
+/// Turns every pixel on and off
+pub struct Blink {
+    pub on: Colour,
+    pub off: Colour,
+    pub on_ms: u32,
+    pub off_ms: u32,
+}
+
+impl Blink {
+    pub fn new(on: Colour, off: Colour, on_ms: u32, off_ms: u32) -> Self {
+        Self {
+            on,
+            off,
+            on_ms,
+            off_ms,
+        }
+    }
+}
+
+impl Effect for Blink {
+    fn colour(&mut self, elapsed_ms: u32, _index: usize, _count: usize) -> Colour {
+        let period_ms = (self.on_ms + self.off_ms).max(1);
+
+        if elapsed_ms % period_ms < self.on_ms {
+            self.on
+        } else {
+            self.off
+        }
+    }
+}
+
+/// Short bright flashes with the pixels off in between
+pub struct Strobe {
+    pub colour: Colour,
+    /// Time from one flash to the next
+    pub period_ms: u32,
+    pub flash_ms: u32,
+}
+
+impl Strobe {
+    pub fn new(colour: Colour, period_ms: u32) -> Self {
+        Self {
+            colour,
+            period_ms,
+            flash_ms: 20,
+        }
+    }
+}
+
+impl Effect for Strobe {
+    fn colour(&mut self, elapsed_ms: u32, _index: usize, _count: usize) -> Colour {
+        if elapsed_ms % self.period_ms.max(1) < self.flash_ms {
+            self.colour
+        } else {
+            Colour::BLACK
+        }
+    }
+}
+
+/// Fills the pixels with a colour one at a time, then wipes them back to the background
+pub struct Wipe {
+    pub colour: Colour,
+    pub background: Colour,
+    /// Time before the next pixel changes
+    pub step_ms: u32,
+}
+
+impl Wipe {
+    pub fn new(colour: Colour, background: Colour, step_ms: u32) -> Self {
+        Self {
+            colour,
+            background,
+            step_ms,
+        }
+    }
+}
+
+impl Effect for Wipe {
+    fn colour(&mut self, elapsed_ms: u32, index: usize, count: usize) -> Colour {
+        let step = (elapsed_ms / self.step_ms.max(1)) as usize % (2 * count.max(1));
+
+        // The first pass fills the pixels and the second clears them
+        let (filled, unfilled) = if step < count {
+            (self.colour, self.background)
+        } else {
+            (self.background, self.colour)
+        };
+
+        if index < step % count.max(1) + 1 {
+            filled
+        } else {
+            unfilled
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Turns readings into colours, for showing a status at a glance
+
+use super::animation::PixelDisplay;
+use super::colour::Colour;
+
+/// Anything that picks a colour for a value
+pub trait ColourMap {
+    fn colour(&self, value: f32) -> Colour;
+}
+
+impl<F: Fn(f32) -> Colour> ColourMap for F {
+    fn colour(&self, value: f32) -> Colour {
+        self(value)
+    }
+}
+
+///
+/// Blends smoothly between colours at set values
+///
+/// Stops must be in ascending order of value. Anything beyond the first or last stop gets that
+/// stop's colour.
+///
+pub struct Gradient<const N: usize> {
+    pub stops: [(f32, Colour); N],
+}
+
+impl<const N: usize> Gradient<N> {
+    pub const fn new(stops: [(f32, Colour); N]) -> Self {
+        Self { stops }
+    }
+}
+
+impl<const N: usize> ColourMap for Gradient<N> {
+    fn colour(&self, value: f32) -> Colour {
+        let (Some(&(first_value, first_colour)), Some(&(_, last_colour))) =
+            (self.stops.first(), self.stops.last())
+        else {
+            return Colour::BLACK;
+        };
+
+        if value <= first_value {
+            return first_colour;
+        }
+
+        for pair in self.stops.windows(2) {
+            let (from_value, from_colour) = pair[0];
+            let (to_value, to_colour) = pair[1];
+
+            if value <= to_value {
+                let amount = (value - from_value) / (to_value - from_value);
+                return from_colour.lerp(to_colour, amount);
+            }
+        }
+
+        last_colour
+    }
+}
+
+///
+/// Picks a colour by which band a value falls in, with no blending
+///
+/// Each band is the colour for values below its limit, checked in order, so limits should be
+/// ascending. Values above every limit get `otherwise`.
+///
+pub struct Thresholds<const N: usize> {
+    pub bands: [(f32, Colour); N],
+    pub otherwise: Colour,
+}
+
+impl<const N: usize> Thresholds<N> {
+    pub const fn new(bands: [(f32, Colour); N], otherwise: Colour) -> Self {
+        Self { bands, otherwise }
+    }
+}
+
+impl<const N: usize> ColourMap for Thresholds<N> {
+    fn colour(&self, value: f32) -> Colour {
+        self.bands
+            .iter()
+            .find(|(limit, _)| value < *limit)
+            .map_or(self.otherwise, |(_, colour)| *colour)
+    }
+}
+
+/// Sets every pixel to the colour for `value` and shows it
+pub fn show_value<D: PixelDisplay, M: ColourMap>(
+    display: &mut D,
+    map: &M,
+    value: f32,
+) -> Result<(), D::Error> {
+    let colour = map.colour(value);
+
+    for index in 0..display.pixel_count() {
+        display.set_colour(index, colour);
+    }
+
+    display.show()
+}
+
+///
+/// Lights up more pixels the further a value is from `min` towards `max`
+///
+/// The last lit pixel is dimmed in proportion to how far into it the value reaches, so three
+/// LEDs can still show small changes. `max` can be less than `min`, such as for a distance
+/// sensor where closer should fill the bar.
+///
+pub struct BarGraph<M: ColourMap> {
+    pub min: f32,
+    pub max: f32,
+    /// Colour of the lit pixels, chosen by the value
+    pub map: M,
+    pub background: Colour,
+}
+
+impl<M: ColourMap> BarGraph<M> {
+    pub fn new(min: f32, max: f32, map: M) -> Self {
+        Self {
+            min,
+            max,
+            map,
+            background: Colour::BLACK,
+        }
+    }
+
+    /// The colour of pixel `index` out of `count` for `value`
+    pub fn colour(&self, value: f32, index: usize, count: usize) -> Colour {
+        if value.is_nan() || self.max == self.min {
+            return self.background;
+        }
+
+        let fraction = ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0);
+        let lit = fraction * count as f32 - index as f32;
+
+        self.background
+            .lerp(self.map.colour(value), lit.clamp(0.0, 1.0))
+    }
+
+    pub fn show<D: PixelDisplay>(&self, display: &mut D, value: f32) -> Result<(), D::Error> {
+        let count = display.pixel_count();
+
+        for index in 0..count {
+            display.set_colour(index, self.colour(value, index, count));
+        }
+
+        display.show()
+    }
+}
@@ -1 +1,6 @@
+pub mod animation;
+pub mod colour;
+pub mod effects;
+pub mod mapping;
 pub mod piicodev_rgb;
+pub mod strip;
@@ -6,16 +6,20 @@ use bsp::hal::i2c::Error;
 use embedded_hal::i2c::I2c;
 
 use crate::i2c::I2CHandler;
+use crate::piicodev_error::PiicoDevError;
+
+use super::animation::PixelDisplay;
+use super::colour::Colour;
 
 // Peripheral address of the PiicoDev RGB device
-const BASE_ADDR: u8 = 0x1E;
-const _DEV_ID: u8 = 0x84;
-const _REG_DEV_ID: u8 = 0x00;
-const _REG_FIRM_VER: u8 = 0x01;
+pub const BASE_ADDR: u8 = 0x1E;
+const DEV_ID: u8 = 0x84;
+const REG_DEV_ID: u8 = 0x00;
+const REG_FIRM_VER: u8 = 0x01;
 // Address of the LED
 const REG_CTRL: u8 = 0x03;
-const _REG_CLEAR: u8 = 0x04;
-const _REG_I2C_ADDR: u8 = 0x05;
+const REG_CLEAR: u8 = 0x04;
+const REG_I2C_ADDR: u8 = 0x05;
 // Address of the brightness controller
 const REG_BRIGHT: u8 = 0x06;
 // Address of where to send LED colour colours
@@ -24,30 +28,82 @@ const REG_LED_VALS: u8 = 0x07;
 // Red-Green-Blue properties
 pub type RGB = (u8, u8, u8);
 
+/// LEDs on each module
+pub const PIXELS: usize = 3;
+
 pub struct PiicoDevRGB<'i2c> {
     addr: u8,
-    led: [RGB; 3],
+    led: [RGB; PIXELS],
     bright: u8,
     i2c: &'i2c RefCell<I2CHandler>,
 }
 
 impl<'i2c> PiicoDevRGB<'i2c> {
-    pub fn new(i2c: &'i2c RefCell<I2CHandler>) -> Self {
+    /// Defaults to `BASE_ADDR`, see `set_i2c_addr` for using several modules
+    pub fn new(addr: Option<u8>, i2c: &'i2c RefCell<I2CHandler>) -> Self {
         Self {
-            addr: BASE_ADDR,
+            addr: addr.unwrap_or(BASE_ADDR),
             led: [(0, 0, 0), (0, 0, 0), (0, 0, 0)],
             bright: 40,
             i2c,
         }
     }
 
-    pub fn set_pixel(&mut self, n: usize, c: RGB) {
-        self.led[n] = c;
+    /// Checks the device at the address really is an RGB module
+    pub fn init(&mut self) -> Result<(), PiicoDevError> {
+        let found = self.read_id()?;
+
+        if found != DEV_ID {
+            return Err(PiicoDevError::UnexpectedDeviceId {
+                expected: DEV_ID,
+                found,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn read_id(&mut self) -> Result<u8, Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        let mut id = [0; 1];
+        i2c.write_read(self.addr, &[REG_DEV_ID], &mut id)?;
+
+        Ok(id[0])
+    }
+
+    /// Major and minor firmware version
+    pub fn read_firmware(&mut self) -> Result<(u8, u8), Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        let mut version = [0; 2];
+        i2c.write_read(self.addr, &[REG_FIRM_VER], &mut version)?;
+
+        Ok((version[0], version[1]))
+    }
+
+    /// Takes a `Colour` or an `RGB` tuple
+    pub fn set_pixel(&mut self, n: usize, c: impl Into<RGB>) {
+        self.led[n] = c.into();
     }
 
-    // fn set_i2c_addr(&mut self, new_addr: u8) -> Result<(), Error> {
-    //     i2c.write(self.addr, &[REG_I2C_ADDR, new_addr])
-    // }
+    pub fn addr(&self) -> u8 {
+        self.addr
+    }
+
+    ///
+    /// Moves the module to a new address, which it remembers after power off
+    ///
+    /// Only have one module at the old address on the bus while doing this.
+    ///
+    pub fn set_i2c_addr(&mut self, new_addr: u8) -> Result<(), Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        i2c.write(self.addr, &[REG_I2C_ADDR, new_addr])?;
+        self.addr = new_addr;
+
+        Ok(())
+    }
 
     pub fn show(&mut self) -> Result<(), Error> {
         let mut i2c = self.i2c.borrow_mut();
@@ -68,22 +124,26 @@ impl<'i2c> PiicoDevRGB<'i2c> {
         i2c.write(self.addr, &buffer)
     }
 
-    // pub fn clear(&mut self) -> Result<(), Error> {
-    //     let mut i2c = self.i2c.borrow_mut();
-    //
-    //     i2c.write(self.addr, &[REG_CLEAR, 0x01])?;
-    //     self.led = [(0, 0, 0), (0, 0, 0), (0, 0, 0)];
-    //
-    //     Ok(())
-    // }
-
-    // pub fn fill(&mut self, c: u8) -> Result<(), Error> {
-    //     for i in 0..self.led.len() {
-    //         self.led[i] = (c, c, c);
-    //     }
-    //
-    //     self.show()
-    // }
+    /// Turns every LED off straight away
+    pub fn clear(&mut self) -> Result<(), Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        i2c.write(self.addr, &[REG_CLEAR, 0x01])?;
+        self.led = [(0, 0, 0), (0, 0, 0), (0, 0, 0)];
+
+        Ok(())
+    }
+
+    /// Sets every LED to the same colour and shows it
+    pub fn fill(&mut self, c: impl Into<RGB>) -> Result<(), Error> {
+        let c = c.into();
+
+        for i in 0..self.led.len() {
+            self.led[i] = c;
+        }
+
+        self.show()
+    }
 
     pub fn set_brightness(&mut self, x: u8) -> Result<(), Error> {
         let mut i2c = self.i2c.borrow_mut();
@@ -103,3 +163,19 @@ impl<'i2c> PiicoDevRGB<'i2c> {
         i2c.write(self.addr, &[REG_CTRL, state_value])
     }
 }
+
+impl<'i2c> PixelDisplay for PiicoDevRGB<'i2c> {
+    type Error = Error;
+
+    fn pixel_count(&self) -> usize {
+        self.led.len()
+    }
+
+    fn set_colour(&mut self, index: usize, colour: Colour) {
+        self.set_pixel(index, colour);
+    }
+
+    fn show(&mut self) -> Result<(), Error> {
+        PiicoDevRGB::show(self)
+    }
+}
@@ -0,0 +1,96 @@
+use rp_pico::hal::i2c::Error;
+
+use super::animation::PixelDisplay;
+use super::colour::Colour;
+use super::piicodev_rgb::{PiicoDevRGB, PIXELS, RGB};
+
+///
+/// Several RGB modules at different addresses, treated as one row of LEDs
+///
+/// Pixels are numbered along the modules in the order given, so with two modules pixel 3 is the
+/// first LED of the second module.
+///
+pub struct RgbStrip<'i2c, const N: usize> {
+    modules: [PiicoDevRGB<'i2c>; N],
+}
+
+impl<'i2c, const N: usize> RgbStrip<'i2c, N> {
+    pub fn new(modules: [PiicoDevRGB<'i2c>; N]) -> Self {
+        Self { modules }
+    }
+
+    pub fn module(&mut self, index: usize) -> &mut PiicoDevRGB<'i2c> {
+        &mut self.modules[index]
+    }
+
+    pub fn pixel_count(&self) -> usize {
+        N * PIXELS
+    }
+
+    /// Takes a `Colour` or an `RGB` tuple. Pixels past the end of the strip are ignored.
+    pub fn set_pixel(&mut self, n: usize, c: impl Into<RGB>) {
+        if let Some(module) = self.modules.get_mut(n / PIXELS) {
+            module.set_pixel(n % PIXELS, c);
+        }
+    }
+
+    /// Sends every module its pixels
+    pub fn show(&mut self) -> Result<(), Error> {
+        for module in self.modules.iter_mut() {
+            module.show()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn fill(&mut self, c: impl Into<RGB>) -> Result<(), Error> {
+        let c = c.into();
+
+        for module in self.modules.iter_mut() {
+            module.fill(c)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn clear(&mut self) -> Result<(), Error> {
+        for module in self.modules.iter_mut() {
+            module.clear()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_brightness(&mut self, x: u8) -> Result<(), Error> {
+        for module in self.modules.iter_mut() {
+            module.set_brightness(x)?;
+        }
+
+        Ok(())
+    }
+
+    /// The power LED on each module
+    pub fn power_led(&mut self, state: bool) -> Result<(), Error> {
+        for module in self.modules.iter_mut() {
+            module.power_led(state)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'i2c, const N: usize> PixelDisplay for RgbStrip<'i2c, N> {
+    type Error = Error;
+
+    fn pixel_count(&self) -> usize {
+        RgbStrip::pixel_count(self)
+    }
+
+    fn set_colour(&mut self, index: usize, colour: Colour) {
+        self.set_pixel(index, colour);
+    }
+
+    fn show(&mut self) -> Result<(), Error> {
+        RgbStrip::show(self)
+    }
+}
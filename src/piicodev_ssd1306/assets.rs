@@ -0,0 +1,8 @@
+//! Images and fonts from the `assets` folder, converted by build.rs
+//!
+//! Drop PBM, XBM or PNG images into `assets/images` and BDF fonts into `assets/fonts`. Each one
+//! becomes a constant named after its file, such as `THERMOMETER` for `thermometer.pbm`.
+
+use super::bitmap::{Bitmap, BitmapFont, Glyph};
+
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
@@ -0,0 +1,134 @@
+//! Images and fonts stored in flash, as generated by build.rs from the `assets` folder
+
+use super::framebuffer::Framebuffer;
+use super::OLEDColour;
+
+///
+/// 1-bit image in the same page layout as the framebuffer
+///
+/// Each byte is a column of 8 pixels, with the top one in the least significant bit.
+///
+#[derive(Copy, Clone)]
+pub struct Bitmap {
+    pub width: u8,
+    pub height: u8,
+    pub data: &'static [u8],
+}
+
+impl Bitmap {
+    pub const fn new(width: u8, height: u8, data: &'static [u8]) -> Self {
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Reads a pixel. Anything outside of the image is black.
+    pub fn get_pixel(&self, x: i32, y: i32) -> OLEDColour {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return OLEDColour::BLACK;
+        }
+
+        let index = x as usize + (y as usize / 8) * self.width as usize;
+
+        match (self.data[index] >> (y % 8)) & 1 {
+            0 => OLEDColour::BLACK,
+            _ => OLEDColour::WHITE,
+        }
+    }
+}
+
+/// A character of a `BitmapFont`
+pub struct Glyph {
+    pub character: char,
+    /// Where the glyph's top left corner is, relative to the top left of the character
+    pub x_offset: i8,
+    pub y_offset: i8,
+    /// How far along the next character starts
+    pub advance: u8,
+    pub bitmap: Bitmap,
+}
+
+/// A proportional font converted from a BDF file
+pub struct BitmapFont {
+    /// Height of a line of text
+    pub height: u8,
+    /// Sorted by character
+    pub glyphs: &'static [Glyph],
+}
+
+impl BitmapFont {
+    pub fn glyph(&self, character: char) -> Option<&Glyph> {
+        self.glyphs
+            .binary_search_by_key(&character, |glyph| glyph.character)
+            .ok()
+            .map(|index| &self.glyphs[index])
+    }
+
+    /// How wide `text` is when drawn. Characters missing from the font take up no space.
+    pub fn text_width(&self, text: &str) -> i32 {
+        text.chars()
+            .filter_map(|character| self.glyph(character))
+            .map(|glyph| glyph.advance as i32)
+            .sum()
+    }
+}
+
+impl Framebuffer {
+    ///
+    /// Draws `bitmap` with its top left corner at `x`, `y`
+    ///
+    /// Like MicroPython's `FrameBuffer.blit`, pixels of the `transparent` colour are skipped,
+    /// leaving whatever was drawn underneath.
+    ///
+    pub fn blit(&mut self, bitmap: &Bitmap, x: i32, y: i32, transparent: Option<OLEDColour>) {
+        for j in 0..bitmap.height as i32 {
+            for i in 0..bitmap.width as i32 {
+                let colour = bitmap.get_pixel(i, j);
+
+                if Some(colour) != transparent {
+                    self.pixel(x + i, y + j, colour);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Writes `text` in `font`, with its top left corner at `x`, `y`
+    ///
+    /// Only the glyphs themselves are drawn, the background is left alone. Returns where the
+    /// next character would go, so text in different fonts can follow on.
+    ///
+    pub fn text_with_font(
+        &mut self,
+        font: &BitmapFont,
+        text: &str,
+        x: i32,
+        y: i32,
+        colour: OLEDColour,
+    ) -> i32 {
+        let mut position = x;
+
+        for character in text.chars() {
+            let Some(glyph) = font.glyph(character) else {
+                continue;
+            };
+
+            let left = position + glyph.x_offset as i32;
+            let top = y + glyph.y_offset as i32;
+
+            for j in 0..glyph.bitmap.height as i32 {
+                for i in 0..glyph.bitmap.width as i32 {
+                    if glyph.bitmap.get_pixel(i, j) == OLEDColour::WHITE {
+                        self.pixel(left + i, top + j, colour);
+                    }
+                }
+            }
+
+            position += glyph.advance as i32;
+        }
+
+        position
+    }
+}
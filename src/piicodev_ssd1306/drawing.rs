@@ -0,0 +1,183 @@
+//! Drawing primitives, matching the PiicoDev SSD1306 MicroPython module
+//!
+//! Shapes can hang off the edge of the display, anything outside of it is clipped.
+
+use embedded_graphics::geometry::Point;
+use embedded_graphics::mono_font::ascii::FONT_5X8;
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::text::{Baseline, Text};
+use embedded_graphics::Drawable;
+use libm::{cosf, sinf};
+
+use super::framebuffer::Framebuffer;
+use super::OLEDColour;
+
+/// Width and height of a character cell used by `text`
+pub const CHARACTER_SIZE: i32 = 8;
+
+impl Framebuffer {
+    pub fn hline(&mut self, x: i32, y: i32, width: i32, colour: OLEDColour) {
+        for x in x..x + width {
+            self.pixel(x, y, colour);
+        }
+    }
+
+    pub fn vline(&mut self, x: i32, y: i32, height: i32, colour: OLEDColour) {
+        for y in y..y + height {
+            self.pixel(x, y, colour);
+        }
+    }
+
+    /// Draws a line between two points, both included, using Bresenham's algorithm
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, colour: OLEDColour) {
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let step_x = if x0 < x1 { 1 } else { -1 };
+        let step_y = if y0 < y1 { 1 } else { -1 };
+
+        let mut error = dx + dy;
+        let mut x = x0;
+        let mut y = y0;
+
+        loop {
+            self.pixel(x, y, colour);
+
+            if x == x1 && y == y1 {
+                break;
+            }
+
+            let doubled = 2 * error;
+
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Outline of a rectangle whose top left corner is at `x`, `y`
+    pub fn rect(&mut self, x: i32, y: i32, width: i32, height: i32, colour: OLEDColour) {
+        if width <= 0 || height <= 0 {
+            return;
+        }
+
+        self.hline(x, y, width, colour);
+        self.hline(x, y + height - 1, width, colour);
+        self.vline(x, y, height, colour);
+        self.vline(x + width - 1, y, height, colour);
+    }
+
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, colour: OLEDColour) {
+        for y in y..y + height {
+            self.hline(x, y, width, colour);
+        }
+    }
+
+    ///
+    /// Draws a circle centred on `x`, `y`
+    ///
+    /// A `thickness` of 1 fills the circle. Anything less draws a ring that fraction of the radius
+    /// thick, with 0 being a 1 pixel outline.
+    ///
+    pub fn circ(&mut self, x: i32, y: i32, r: i32, thickness: f32, colour: OLEDColour) {
+        let outer = r * r;
+        let inner = r as f32 - r as f32 * thickness - 1.0;
+        let inner = inner * inner;
+
+        for i in x - r..=x + r {
+            for j in y - r..=y + r {
+                let distance = (i - x) * (i - x) + (j - y) * (j - y);
+
+                if distance >= outer {
+                    continue;
+                }
+
+                if thickness == 1.0 || distance as f32 >= inner {
+                    self.pixel(i, j, colour);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Draws part of a ring centred on `x`, `y`, from `start_angle` up to `end_angle` in degrees
+    ///
+    /// Angles go clockwise from the positive x axis. `thickness` is a fraction of the radius,
+    /// with 0 being 1 pixel thick.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc(
+        &mut self,
+        x: i32,
+        y: i32,
+        r: i32,
+        start_angle: i32,
+        end_angle: i32,
+        thickness: f32,
+        colour: OLEDColour,
+    ) {
+        let inner = (r as f32 * (1.0 - thickness)) as i32 - 1;
+
+        for i in inner.max(0)..r {
+            for angle in start_angle..end_angle {
+                let radians = (angle as f32).to_radians();
+                let arc_x = (i as f32 * cosf(radians) + x as f32) as i32;
+                let arc_y = (i as f32 * sinf(radians) + y as f32) as i32;
+
+                self.pixel(arc_x, arc_y, colour);
+            }
+        }
+    }
+
+    ///
+    /// Moves the whole image by `dx`, `dy`, like MicroPython's `FrameBuffer.scroll`
+    ///
+    /// This moves the framebuffer, rather than scrolling the display in hardware. The area moved
+    /// away from keeps its old contents.
+    ///
+    pub fn scroll(&mut self, dx: i32, dy: i32) {
+        let width = self.width() as i32;
+        let height = self.height() as i32;
+
+        // Copy in the opposite direction to the move, so nothing is overwritten before it's read
+        for j in 0..height {
+            let y = if dy > 0 { height - 1 - j } else { j };
+
+            for i in 0..width {
+                let x = if dx > 0 { width - 1 - i } else { i };
+                let (from_x, from_y) = (x - dx, y - dy);
+
+                if from_x >= 0 && from_x < width && from_y >= 0 && from_y < height {
+                    let colour = self.get_pixel(from_x, from_y);
+                    self.pixel(x, y, colour);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Writes `text` with its top left corner at `x`, `y`
+    ///
+    /// Characters sit on an 8x8 grid like the MicroPython framebuffer font, so text lines up
+    /// the same way it does in the PiicoDev examples. Only the character cells are drawn, the
+    /// background is left alone.
+    ///
+    pub fn text(&mut self, text: &str, x: i32, y: i32, colour: OLEDColour) {
+        let style = MonoTextStyle::new(&FONT_5X8, BinaryColor::from(colour));
+        let mut buffer = [0; 4];
+
+        for (i, character) in text.chars().enumerate() {
+            let position = Point::new(x + i as i32 * CHARACTER_SIZE, y);
+            let character = character.encode_utf8(&mut buffer);
+
+            // Drawing into the framebuffer can't fail
+            let _ = Text::with_baseline(character, position, style, Baseline::Top).draw(self);
+        }
+    }
+}
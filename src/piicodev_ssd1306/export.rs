@@ -0,0 +1,58 @@
+//! Dumps the framebuffer as a PBM image, for screenshots and comparing against known good images
+
+use core::fmt::{self, Write};
+
+use super::framebuffer::Framebuffer;
+use super::OLEDColour;
+
+/// PBM readers aren't required to handle longer lines
+const PBM_LINE_LENGTH: usize = 64;
+
+/// Surround a screenshot sent over the console, so it can be cut out of the rest of the log
+pub const SCREENSHOT_START: &str = "-----BEGIN SCREENSHOT-----";
+pub const SCREENSHOT_END: &str = "-----END SCREENSHOT-----";
+
+impl Framebuffer {
+    ///
+    /// Writes the image as a plain (P1) PBM, as it appears in the current rotation
+    ///
+    /// Lit pixels are written as black, the same way images in `assets` are read in. The output
+    /// only depends on what was drawn, so it can be compared byte for byte.
+    ///
+    pub fn write_pbm<W: Write>(&self, out: &mut W) -> fmt::Result {
+        let width = self.width() as i32;
+        let height = self.height() as i32;
+
+        writeln!(out, "P1")?;
+        writeln!(out, "{} {}", width, height)?;
+
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = match self.get_pixel(x, y) {
+                    OLEDColour::WHITE => '1',
+                    OLEDColour::BLACK => '0',
+                };
+
+                out.write_char(pixel)?;
+
+                if ((x + 1) as usize).is_multiple_of(PBM_LINE_LENGTH) || x == width - 1 {
+                    out.write_char('\n')?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Writes the image as a PBM between `SCREENSHOT_START` and `SCREENSHOT_END` lines
+    ///
+    /// Meant for sending over the UART console, where the image can be cut out of the log with
+    /// `sed -n '/BEGIN SCREENSHOT/,/END SCREENSHOT/{//!p}' log.txt > screenshot.pbm`.
+    ///
+    pub fn write_screenshot<W: Write>(&self, out: &mut W) -> fmt::Result {
+        writeln!(out, "{}", SCREENSHOT_START)?;
+        self.write_pbm(out)?;
+        writeln!(out, "{}", SCREENSHOT_END)
+    }
+}
@@ -0,0 +1,219 @@
+use core::convert::Infallible;
+
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{OriginDimensions, Size};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::Pixel;
+
+use super::geometry::{DisplayRotation, DisplaySize};
+use super::OLEDColour;
+
+/// Size of the largest panel, which the buffer is sized for
+pub const MAX_WIDTH: u8 = 128;
+pub const MAX_HEIGHT: u8 = 64;
+
+/// Each page is a horizontal strip 8 pixels tall
+pub const MAX_PAGES: u8 = MAX_HEIGHT / 8;
+
+pub const BUFFER_SIZE: usize = MAX_WIDTH as usize * MAX_PAGES as usize; // 1024
+
+///
+/// 1-bit image laid out the same way as the SSD1306's display RAM
+///
+/// Each byte is a column of 8 vertical pixels within a page, with the least significant bit at
+/// the top. Bytes run left to right across a page, then on to the next page down.
+///
+/// Changed columns are tracked per page, so only those need to be sent to the display.
+///
+/// Coordinates are in the rotated orientation. For 90° and 270° they are mapped onto the panel
+/// here, so drawing code never needs to know.
+///
+pub struct Framebuffer {
+    buffer: [u8; BUFFER_SIZE],
+    size: DisplaySize,
+    rotation: DisplayRotation,
+    /// First and last changed column of each page
+    dirty: [Option<(u8, u8)>; MAX_PAGES as usize],
+}
+
+impl Framebuffer {
+    /// Starts off entirely dirty, as the display's RAM holds garbage on power up
+    pub fn new(size: DisplaySize) -> Self {
+        let mut framebuffer = Self {
+            buffer: [0; BUFFER_SIZE],
+            size,
+            rotation: DisplayRotation::Rotate0,
+            dirty: [None; MAX_PAGES as usize],
+        };
+
+        framebuffer.mark_all_dirty();
+
+        framebuffer
+    }
+
+    pub fn display_size(&self) -> DisplaySize {
+        self.size
+    }
+
+    pub fn rotation(&self) -> DisplayRotation {
+        self.rotation
+    }
+
+    /// Changes how coordinates map onto the panel. Whatever is already drawn stays where it is.
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) {
+        self.rotation = rotation;
+    }
+
+    /// Width in the current rotation
+    pub fn width(&self) -> u8 {
+        match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => self.size.width(),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => self.size.height(),
+        }
+    }
+
+    /// Height in the current rotation
+    pub fn height(&self) -> u8 {
+        match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => self.size.height(),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => self.size.width(),
+        }
+    }
+
+    /// Maps a rotated coordinate onto the panel, or `None` if it's off the display
+    fn to_panel(&self, x: i32, y: i32) -> Option<(usize, usize)> {
+        if x < 0 || y < 0 || x >= self.width() as i32 || y >= self.height() as i32 {
+            return None;
+        }
+
+        let panel_width = self.size.width() as i32;
+        let panel_height = self.size.height() as i32;
+
+        let (x, y) = match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (x, y),
+            DisplayRotation::Rotate90 => (panel_width - 1 - y, x),
+            DisplayRotation::Rotate270 => (y, panel_height - 1 - x),
+        };
+
+        Some((x as usize, y as usize))
+    }
+
+    fn mark_dirty(&mut self, page: usize, column: u8) {
+        self.dirty[page] = match self.dirty[page] {
+            Some((start, end)) => Some((start.min(column), end.max(column))),
+            None => Some((column, column)),
+        };
+    }
+
+    /// Forces the whole display to be sent on the next update
+    pub fn mark_all_dirty(&mut self) {
+        for page in 0..self.size.pages() as usize {
+            self.dirty[page] = Some((0, self.size.width() - 1));
+        }
+    }
+
+    /// The first and last changed column of a page since it was last taken, clearing it
+    pub fn take_dirty(&mut self, page: u8) -> Option<(u8, u8)> {
+        self.dirty[page as usize].take()
+    }
+
+    /// Sets a pixel. Anything outside of the display is ignored.
+    pub fn pixel(&mut self, x: i32, y: i32, colour: OLEDColour) {
+        let Some((x, y)) = self.to_panel(x, y) else {
+            return;
+        };
+
+        let page = y / 8;
+        let index = x + page * self.size.width() as usize;
+        let bit = 1 << (y % 8);
+
+        let byte = match colour {
+            OLEDColour::WHITE => self.buffer[index] | bit,
+            OLEDColour::BLACK => self.buffer[index] & !bit,
+        };
+
+        if byte != self.buffer[index] {
+            self.buffer[index] = byte;
+            self.mark_dirty(page, x as u8);
+        }
+    }
+
+    /// Reads a pixel. Anything outside of the display is black.
+    pub fn get_pixel(&self, x: i32, y: i32) -> OLEDColour {
+        let Some((x, y)) = self.to_panel(x, y) else {
+            return OLEDColour::BLACK;
+        };
+
+        let index = x + (y / 8) * self.size.width() as usize;
+
+        match (self.buffer[index] >> (y % 8)) & 1 {
+            0 => OLEDColour::BLACK,
+            _ => OLEDColour::WHITE,
+        }
+    }
+
+    pub fn fill(&mut self, colour: OLEDColour) {
+        let byte = match colour {
+            OLEDColour::BLACK => 0x00,
+            OLEDColour::WHITE => 0xFF,
+        };
+
+        let length = self.size.width() as usize * self.size.pages() as usize;
+        self.buffer[..length].fill(byte);
+        self.mark_all_dirty();
+    }
+
+    /// The bytes of a single page, as they are sent to the display
+    pub fn page(&self, page: u8) -> &[u8] {
+        let width = self.size.width() as usize;
+        let start = page as usize * width;
+
+        &self.buffer[start..start + width]
+    }
+}
+
+impl From<BinaryColor> for OLEDColour {
+    fn from(colour: BinaryColor) -> Self {
+        match colour {
+            BinaryColor::Off => OLEDColour::BLACK,
+            BinaryColor::On => OLEDColour::WHITE,
+        }
+    }
+}
+
+impl From<OLEDColour> for BinaryColor {
+    fn from(colour: OLEDColour) -> Self {
+        match colour {
+            OLEDColour::BLACK => BinaryColor::Off,
+            OLEDColour::WHITE => BinaryColor::On,
+        }
+    }
+}
+
+impl OriginDimensions for Framebuffer {
+    fn size(&self) -> Size {
+        Size::new(self.width() as u32, self.height() as u32)
+    }
+}
+
+impl DrawTarget for Framebuffer {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, colour) in pixels {
+            self.pixel(point.x, point.y, colour.into());
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, colour: Self::Color) -> Result<(), Self::Error> {
+        self.fill(colour.into());
+
+        Ok(())
+    }
+}
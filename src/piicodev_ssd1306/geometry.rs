@@ -0,0 +1,64 @@
+/// Panel resolutions the SSD1306 is commonly paired with
+#[derive(Copy, Clone, PartialEq)]
+pub enum DisplaySize {
+    /// The PiicoDev OLED module
+    Size128x64,
+    Size128x32,
+    Size96x16,
+    Size72x40,
+    Size64x48,
+}
+
+impl DisplaySize {
+    pub fn width(&self) -> u8 {
+        match self {
+            Self::Size128x64 | Self::Size128x32 => 128,
+            Self::Size96x16 => 96,
+            Self::Size72x40 => 72,
+            Self::Size64x48 => 64,
+        }
+    }
+
+    pub fn height(&self) -> u8 {
+        match self {
+            Self::Size128x64 => 64,
+            Self::Size128x32 => 32,
+            Self::Size96x16 => 16,
+            Self::Size72x40 => 40,
+            Self::Size64x48 => 48,
+        }
+    }
+
+    pub fn pages(&self) -> u8 {
+        self.height() / 8
+    }
+
+    /// COM pins hardware configuration, depending on how the panel's rows are wired
+    pub fn com_pins(&self) -> u8 {
+        match self {
+            Self::Size128x32 | Self::Size96x16 => 0x02,
+            Self::Size128x64 | Self::Size72x40 | Self::Size64x48 => 0x12,
+        }
+    }
+
+    /// Smaller panels are wired to the middle of the controller's 128 columns
+    pub fn column_offset(&self) -> u8 {
+        match self {
+            Self::Size72x40 => 28,
+            Self::Size64x48 => 32,
+            _ => 0,
+        }
+    }
+}
+
+/// Clockwise rotation of the image on the panel
+#[derive(Copy, Clone, PartialEq)]
+pub enum DisplayRotation {
+    Rotate0,
+    /// Done in software by the framebuffer, swapping width and height
+    Rotate90,
+    /// Done in hardware by flipping the column and row scan directions
+    Rotate180,
+    /// Done in software by the framebuffer, swapping width and height
+    Rotate270,
+}
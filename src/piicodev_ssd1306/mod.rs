@@ -0,0 +1,324 @@
+pub mod assets;
+mod bitmap;
+mod drawing;
+mod export;
+mod framebuffer;
+mod geometry;
+mod scroll;
+mod terminal;
+mod widgets;
+
+use core::cell::RefCell;
+use core::convert::Infallible;
+
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{OriginDimensions, Size};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::Pixel;
+use embedded_hal::i2c::I2c;
+
+pub use self::bitmap::{Bitmap, BitmapFont, Glyph};
+pub use self::drawing::CHARACTER_SIZE;
+pub use self::export::{SCREENSHOT_END, SCREENSHOT_START};
+pub use self::framebuffer::{Framebuffer, MAX_WIDTH};
+pub use self::geometry::{DisplayRotation, DisplaySize};
+pub use self::scroll::{FadeMode, ScrollDirection, ScrollInterval};
+pub use self::terminal::Terminal;
+pub use self::widgets::{BarGauge, CompassDial, Sparkline};
+
+pub const BASE_ADDR: u8 = 0x3C;
+/// Used when the address jumper on the back of the module is closed
+pub const ALTERNATE_ADDR: u8 = 0x3D;
+const _SET_CONTRAST: u8 = 0x81;
+const _SET_ENTIRE_ON: u8 = 0xA4;
+const _SET_NORM_INV: u8 = 0xA6;
+const _SET_DISP: u8 = 0xAE;
+const _SET_MEM_ADDR: u8 = 0x20;
+const _SET_COL_ADDR: u8 = 0x21;
+const _SET_PAGE_ADDR: u8 = 0x22;
+const _SET_DISP_START_LINE: u8 = 0x40;
+const _SET_SEG_REMAP: u8 = 0xA0;
+const _SET_MUX_RATIO: u8 = 0xA8;
+const _SET_IREF_SELECT: u8 = 0xAD;
+const _SET_COM_OUT_DIR: u8 = 0xC0;
+const _SET_DISP_OFFSET: u8 = 0xD3;
+const _SET_COM_PIN_CFG: u8 = 0xDA;
+const _SET_DISP_CLK_DIV: u8 = 0xD5;
+const _SET_PRECHARGE: u8 = 0xD9;
+const _SET_VCOM_DESEL: u8 = 0xDB;
+const _SET_CHARGE_PUMP: u8 = 0x8D;
+
+/// Control byte that marks the rest of a write as commands
+const COMMAND_CONTROL: u8 = 0x00;
+/// Control byte that marks the rest of a write as display data
+const DATA_CONTROL: u8 = 0x40;
+
+/// The address for the address jumper (ASW) position, like the MicroPython module's `asw`
+pub const fn address_from_switch(asw: u8) -> u8 {
+    match asw & 1 {
+        0 => BASE_ADDR,
+        _ => ALTERNATE_ADDR,
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum OLEDColour {
+    BLACK = 0,
+    WHITE = 1,
+}
+
+impl From<OLEDColour> for u8 {
+    fn from(colour: OLEDColour) -> u8 {
+        match colour {
+            OLEDColour::BLACK => 0,
+            OLEDColour::WHITE => 1,
+        }
+    }
+}
+
+///
+/// Driver for the SSD1306 OLED controller, drawing into a framebuffer in RAM
+///
+/// Works on any `embedded_hal` I2C bus, so screens can also be drawn against a mock bus on the
+/// host and compared with known good images.
+///
+pub struct PiicoDevSSD1306<'i2c, I> {
+    addr: u8,
+    i2c: &'i2c RefCell<I>,
+    framebuffer: Framebuffer,
+    scrolling: bool,
+}
+
+impl<'i2c, I: I2c> PiicoDevSSD1306<'i2c, I> {
+    ///
+    /// Defaults to `BASE_ADDR` and the 128x64 panel on the PiicoDev module
+    ///
+    /// Two displays can share a bus by closing the address jumper on one of them, each getting
+    /// its own framebuffer.
+    ///
+    pub fn new(addr: Option<u8>, size: Option<DisplaySize>, i2c: &'i2c RefCell<I>) -> Self {
+        let addr = addr.unwrap_or(BASE_ADDR);
+        let size = size.unwrap_or(DisplaySize::Size128x64);
+
+        Self {
+            addr,
+            i2c,
+            framebuffer: Framebuffer::new(size),
+            scrolling: false,
+        }
+    }
+
+    pub fn addr(&self) -> u8 {
+        self.addr
+    }
+
+    /// What `show` sends to the display
+    pub fn framebuffer(&self) -> &Framebuffer {
+        &self.framebuffer
+    }
+
+    /// For drawing with the framebuffer's own methods, like `text` and `blit`
+    pub fn framebuffer_mut(&mut self) -> &mut Framebuffer {
+        &mut self.framebuffer
+    }
+
+    /// Column and row scan direction commands for the current rotation
+    fn scan_direction_cmds(&self) -> [u8; 2] {
+        let flip = self.framebuffer.rotation() == DisplayRotation::Rotate180;
+
+        if flip {
+            // column addr 0 mapped to SEG0, scan from COM0 to COM[N]
+            return [_SET_SEG_REMAP, _SET_COM_OUT_DIR];
+        }
+
+        // column addr 127 mapped to SEG0, scan from COM[N] to COM0
+        [_SET_SEG_REMAP | 0x01, _SET_COM_OUT_DIR | 0x08]
+    }
+
+    /// Initialise the display
+    pub fn init(&mut self) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        let size = self.framebuffer.display_size();
+        let [seg_remap, com_out_dir] = self.scan_direction_cmds();
+
+        for cmd in [
+            _SET_DISP, // display off
+            // address setting
+            _SET_MEM_ADDR,
+            0x00, // horizontal
+            // resolution and layout
+            _SET_DISP_START_LINE, // start at line 0
+            seg_remap,
+            _SET_MUX_RATIO,
+            size.height() - 1,
+            com_out_dir,
+            _SET_DISP_OFFSET,
+            0x00,
+            _SET_COM_PIN_CFG,
+            size.com_pins(),
+            // timing and driving scheme
+            _SET_DISP_CLK_DIV,
+            0x80,
+            _SET_PRECHARGE,
+            0xF1,
+            _SET_VCOM_DESEL,
+            0x30, // 0.83*Vcc
+            // display
+            _SET_CONTRAST,
+            0xFF,           // maximum
+            _SET_ENTIRE_ON, // output follows RAM contents
+            _SET_NORM_INV,  // not inverted
+            _SET_IREF_SELECT,
+            0x30, // enable internal IREF during display on
+            // charge pump
+            _SET_CHARGE_PUMP,
+            0x14,
+            _SET_DISP | 0x01, // display on
+        ] {
+            self.write_cmd(cmd, &mut i2c)?;
+        }
+
+        // The display's RAM is in an unknown state, so everything needs sending
+        self.framebuffer.mark_all_dirty();
+
+        Ok(())
+    }
+
+    fn write_cmd(&mut self, command: u8, i2c: &mut I) -> Result<(), I::Error> {
+        i2c.write(self.addr, &[0x80, command])
+    }
+
+    /// Sends a run of commands in a single write
+    fn write_cmds(&mut self, commands: &[u8], i2c: &mut I) -> Result<(), I::Error> {
+        let mut buffer = [0; 8];
+        buffer[0] = COMMAND_CONTROL;
+        buffer[1..=commands.len()].copy_from_slice(commands);
+
+        i2c.write(self.addr, &buffer[..=commands.len()])
+    }
+
+    /// Sends one page's columns `x0` to `x1` inclusive
+    fn write_window(&mut self, page: u8, x0: u8, x1: u8, i2c: &mut I) -> Result<(), I::Error> {
+        let offset = self.framebuffer.display_size().column_offset();
+        let (start, end) = (x0 + offset, x1 + offset);
+        self.write_cmds(
+            &[_SET_COL_ADDR, start, end, _SET_PAGE_ADDR, page, page],
+            i2c,
+        )?;
+
+        let length = (x1 - x0) as usize + 1;
+        let mut data = [0; MAX_WIDTH as usize + 1];
+        data[0] = DATA_CONTROL;
+        data[1..=length].copy_from_slice(&self.framebuffer.page(page)[x0 as usize..=x1 as usize]);
+
+        i2c.write(self.addr, &data[..=length])
+    }
+
+    ///
+    /// Sends the parts of the framebuffer that have changed since the last call
+    ///
+    /// Each page with changes is sent as its own window, spanning just the changed columns. A
+    /// hardware scroll is stopped first, as writing to the display's RAM during one corrupts it.
+    ///
+    pub fn show(&mut self) -> Result<(), I::Error> {
+        if self.scrolling {
+            self.stop_scroll()?;
+        }
+
+        let mut i2c = self.i2c.borrow_mut();
+
+        for page in 0..self.framebuffer.display_size().pages() {
+            let Some((x0, x1)) = self.framebuffer.take_dirty(page) else {
+                continue;
+            };
+
+            if let Err(error) = self.write_window(page, x0, x1, &mut i2c) {
+                // Whatever didn't make it needs to be sent next time
+                self.framebuffer.mark_all_dirty();
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends the entire framebuffer, whether it has changed or not
+    pub fn show_all(&mut self) -> Result<(), I::Error> {
+        self.framebuffer.mark_all_dirty();
+        self.show()
+    }
+
+    pub fn power_off(&mut self) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        self.write_cmd(_SET_DISP, &mut i2c)
+    }
+
+    pub fn power_on(&mut self) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        self.write_cmd(_SET_DISP | 0x01, &mut i2c)
+    }
+
+    pub fn set_contrast(&mut self, contrast: u8) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        self.write_cmd(_SET_CONTRAST, &mut i2c)?;
+        self.write_cmd(contrast, &mut i2c)
+    }
+
+    pub fn invert(&mut self, invert: u8) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        self.write_cmd(_SET_NORM_INV | (invert & 1), &mut i2c)
+    }
+
+    /// Matches the MicroPython module, where 1 is the normal orientation and 0 is upside down
+    pub fn rotate(&mut self, rotate: u8) -> Result<(), I::Error> {
+        let rotation = match rotate & 1 {
+            1 => DisplayRotation::Rotate0,
+            _ => DisplayRotation::Rotate180,
+        };
+
+        self.set_rotation(rotation)
+    }
+
+    ///
+    /// Rotates everything drawn from now on
+    ///
+    /// 180° is done by the display itself, so it also flips what's already shown. 90° and 270°
+    /// are done by the framebuffer and need the screen redrawing.
+    ///
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        self.framebuffer.set_rotation(rotation);
+
+        let [seg_remap, com_out_dir] = self.scan_direction_cmds();
+        self.write_cmds(&[seg_remap, com_out_dir], &mut i2c)
+    }
+}
+
+impl<'i2c, I> OriginDimensions for PiicoDevSSD1306<'i2c, I> {
+    fn size(&self) -> Size {
+        self.framebuffer.size()
+    }
+}
+
+/// Draws into the framebuffer. Nothing appears on the display until `show` is called.
+impl<'i2c, I> DrawTarget for PiicoDevSSD1306<'i2c, I> {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<P>(&mut self, pixels: P) -> Result<(), Self::Error>
+    where
+        P: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.framebuffer.draw_iter(pixels)
+    }
+
+    fn clear(&mut self, colour: Self::Color) -> Result<(), Self::Error> {
+        self.framebuffer.clear(colour)
+    }
+}
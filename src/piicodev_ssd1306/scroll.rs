@@ -0,0 +1,202 @@
+//! Scrolling and fading done by the display itself, without sending any more image data
+
+use embedded_hal::i2c::I2c;
+
+use super::PiicoDevSSD1306;
+
+const _SET_FADE_BLINK: u8 = 0x23;
+const _RIGHT_HORIZONTAL_SCROLL: u8 = 0x26;
+const _LEFT_HORIZONTAL_SCROLL: u8 = 0x27;
+const _VERTICAL_RIGHT_HORIZONTAL_SCROLL: u8 = 0x29;
+const _VERTICAL_LEFT_HORIZONTAL_SCROLL: u8 = 0x2A;
+const _DEACTIVATE_SCROLL: u8 = 0x2E;
+const _ACTIVATE_SCROLL: u8 = 0x2F;
+const _SET_VERTICAL_SCROLL_AREA: u8 = 0xA3;
+const _SET_ZOOM_IN: u8 = 0xD6;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum ScrollDirection {
+    Left,
+    Right,
+}
+
+/// Time between each step, in frames. The display runs at roughly 100 frames a second.
+#[derive(Copy, Clone, PartialEq)]
+pub enum ScrollInterval {
+    Frames2,
+    Frames3,
+    Frames4,
+    Frames5,
+    Frames25,
+    Frames64,
+    Frames128,
+    Frames256,
+}
+
+impl ScrollInterval {
+    fn bits(&self) -> u8 {
+        match self {
+            Self::Frames5 => 0b000,
+            Self::Frames64 => 0b001,
+            Self::Frames128 => 0b010,
+            Self::Frames256 => 0b011,
+            Self::Frames3 => 0b100,
+            Self::Frames4 => 0b101,
+            Self::Frames25 => 0b110,
+            Self::Frames2 => 0b111,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum FadeMode {
+    Off,
+    /// Steps the contrast down to nothing, then stays off
+    FadeOut,
+    /// Steps the contrast down and back up, over and over
+    Blink,
+}
+
+impl FadeMode {
+    fn bits(&self) -> u8 {
+        match self {
+            Self::Off => 0b00,
+            Self::FadeOut => 0b10,
+            Self::Blink => 0b11,
+        }
+    }
+}
+
+///
+/// Hardware scrolling
+///
+/// Scrolling moves what's in the display's RAM, not the framebuffer, so pages are relative to
+/// the panel whatever the rotation. Stopping a scroll leaves the RAM in a mess, so the whole
+/// framebuffer is sent again on the next `show`. Calling `show` while scrolling stops the scroll.
+///
+impl<'i2c, I: I2c> PiicoDevSSD1306<'i2c, I> {
+    pub fn is_scrolling(&self) -> bool {
+        self.scrolling
+    }
+
+    /// Continuously scrolls pages `start_page` to `end_page` inclusive, wrapping around
+    pub fn scroll_horizontal(
+        &mut self,
+        direction: ScrollDirection,
+        start_page: u8,
+        end_page: u8,
+        interval: ScrollInterval,
+    ) -> Result<(), I::Error> {
+        let command = match direction {
+            ScrollDirection::Left => _LEFT_HORIZONTAL_SCROLL,
+            ScrollDirection::Right => _RIGHT_HORIZONTAL_SCROLL,
+        };
+
+        let last_page = self.framebuffer.display_size().pages() - 1;
+
+        self.start_scroll(&[
+            command,
+            0x00, // dummy byte
+            start_page.min(last_page),
+            interval.bits(),
+            end_page.min(last_page),
+            0x00,
+            0xFF,
+        ])
+    }
+
+    ///
+    /// Scrolls horizontally while also moving up by `vertical_offset` rows each step
+    ///
+    /// Only the rows set with `set_vertical_scroll_area` move vertically, which is the whole
+    /// display by default.
+    ///
+    pub fn scroll_diagonal(
+        &mut self,
+        direction: ScrollDirection,
+        start_page: u8,
+        end_page: u8,
+        interval: ScrollInterval,
+        vertical_offset: u8,
+    ) -> Result<(), I::Error> {
+        let command = match direction {
+            ScrollDirection::Left => _VERTICAL_LEFT_HORIZONTAL_SCROLL,
+            ScrollDirection::Right => _VERTICAL_RIGHT_HORIZONTAL_SCROLL,
+        };
+
+        let size = self.framebuffer.display_size();
+        let last_page = size.pages() - 1;
+
+        self.start_scroll(&[
+            command,
+            0x00, // dummy byte
+            start_page.min(last_page),
+            interval.bits(),
+            end_page.min(last_page),
+            vertical_offset % size.height(),
+        ])
+    }
+
+    ///
+    /// Limits vertical scrolling to `rows` rows, starting `fixed_rows` from the top
+    ///
+    /// The rows above stay where they are, which is handy for keeping a heading in place.
+    ///
+    pub fn set_vertical_scroll_area(&mut self, fixed_rows: u8, rows: u8) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        let height = self.framebuffer.display_size().height();
+        let fixed_rows = fixed_rows.min(height);
+        let rows = rows.min(height - fixed_rows);
+
+        self.write_cmds(&[_SET_VERTICAL_SCROLL_AREA, fixed_rows, rows], &mut i2c)
+    }
+
+    fn start_scroll(&mut self, setup: &[u8]) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        // The scroll can only be set up while it's stopped
+        self.write_cmd(_DEACTIVATE_SCROLL, &mut i2c)?;
+        self.write_cmds(setup, &mut i2c)?;
+        self.write_cmd(_ACTIVATE_SCROLL, &mut i2c)?;
+
+        self.scrolling = true;
+
+        Ok(())
+    }
+
+    pub fn stop_scroll(&mut self) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        self.write_cmd(_DEACTIVATE_SCROLL, &mut i2c)?;
+
+        self.scrolling = false;
+        self.framebuffer.mark_all_dirty();
+
+        Ok(())
+    }
+
+    ///
+    /// Fades the display out, or blinks it, by stepping the contrast
+    ///
+    /// The contrast changes every `interval` + 1 lots of 8 frames, up to 16 lots. It returns to
+    /// whatever `set_contrast` last set once the mode is turned off.
+    ///
+    pub fn set_fade(&mut self, mode: FadeMode, interval: u8) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        let setting = (mode.bits() << 4) | (interval & 0x0F);
+        self.write_cmds(&[_SET_FADE_BLINK, setting], &mut i2c)
+    }
+
+    ///
+    /// Doubles the height of each row, showing the top half of the display over the whole panel
+    ///
+    /// Only works with panels using the alternative COM pin configuration, such as 128x64.
+    ///
+    pub fn set_zoom(&mut self, zoom: bool) -> Result<(), I::Error> {
+        let mut i2c = self.i2c.borrow_mut();
+
+        self.write_cmds(&[_SET_ZOOM_IN, zoom as u8], &mut i2c)
+    }
+}
@@ -0,0 +1,161 @@
+use core::fmt;
+
+use embedded_hal::i2c::I2c;
+
+use super::drawing::CHARACTER_SIZE;
+use super::{OLEDColour, PiicoDevSSD1306};
+
+/// Spaces a tab moves along to the next multiple of
+const TAB_WIDTH: u8 = 4;
+
+///
+/// A tiny text terminal, writing through `write!` and `writeln!`
+///
+/// Text fills an 8x8 character grid, wrapping at the end of each line. Once the bottom line is
+/// full everything scrolls up a line. Like the rest of the display, nothing appears until `show`
+/// is called, so a whole batch of lines can be written at once.
+///
+pub struct Terminal<'display, 'i2c, I> {
+    display: &'display mut PiicoDevSSD1306<'i2c, I>,
+    column: u8,
+    row: u8,
+    cursor: bool,
+}
+
+impl<'display, 'i2c, I: I2c> Terminal<'display, 'i2c, I> {
+    /// Takes over the display, clearing it
+    pub fn new(display: &'display mut PiicoDevSSD1306<'i2c, I>) -> Self {
+        let mut terminal = Self {
+            display,
+            column: 0,
+            row: 0,
+            cursor: false,
+        };
+
+        terminal.clear();
+
+        terminal
+    }
+
+    pub fn columns(&self) -> u8 {
+        self.display.framebuffer().width() / CHARACTER_SIZE as u8
+    }
+
+    pub fn rows(&self) -> u8 {
+        self.display.framebuffer().height() / CHARACTER_SIZE as u8
+    }
+
+    /// Blanks the screen and moves back to the top left
+    pub fn clear(&mut self) {
+        self.display.framebuffer_mut().fill(OLEDColour::BLACK);
+        self.column = 0;
+        self.row = 0;
+        self.toggle_cursor();
+    }
+
+    /// Shows a block where the next character will go
+    pub fn set_cursor(&mut self, cursor: bool) {
+        if cursor == self.cursor {
+            return;
+        }
+
+        self.toggle_cursor();
+        self.cursor = cursor;
+        self.toggle_cursor();
+    }
+
+    /// Sends everything written so far to the display
+    pub fn show(&mut self) -> Result<(), I::Error> {
+        self.display.show()
+    }
+
+    /// Gives the display back, for drawing over the text
+    pub fn display(&mut self) -> &mut PiicoDevSSD1306<'i2c, I> {
+        self.display
+    }
+
+    /// The cursor inverts its cell, so toggling it twice leaves the cell as it was
+    fn toggle_cursor(&mut self) {
+        // Nowhere to show it while waiting to wrap
+        if !self.cursor || self.column >= self.columns() {
+            return;
+        }
+
+        let x = self.column as i32 * CHARACTER_SIZE;
+        let y = self.row as i32 * CHARACTER_SIZE;
+
+        let framebuffer = self.display.framebuffer_mut();
+
+        for j in y..y + CHARACTER_SIZE {
+            for i in x..x + CHARACTER_SIZE {
+                let colour = match framebuffer.get_pixel(i, j) {
+                    OLEDColour::BLACK => OLEDColour::WHITE,
+                    OLEDColour::WHITE => OLEDColour::BLACK,
+                };
+
+                framebuffer.pixel(i, j, colour);
+            }
+        }
+    }
+
+    fn new_line(&mut self) {
+        self.column = 0;
+
+        if self.row + 1 < self.rows() {
+            self.row += 1;
+            return;
+        }
+
+        let framebuffer = self.display.framebuffer_mut();
+        let width = framebuffer.width() as i32;
+        let bottom = self.row as i32 * CHARACTER_SIZE;
+
+        framebuffer.scroll(0, -CHARACTER_SIZE);
+        framebuffer.fill_rect(0, bottom, width, CHARACTER_SIZE, OLEDColour::BLACK);
+    }
+
+    fn put_char(&mut self, character: char) {
+        match character {
+            '\n' => self.new_line(),
+            '\r' => self.column = 0,
+            '\t' => {
+                let next = (self.column / TAB_WIDTH + 1) * TAB_WIDTH;
+
+                while self.column < next.min(self.columns()) {
+                    self.put_char(' ');
+                }
+            }
+            _ => {
+                // Wrapping waits for the next character, so a full line followed by a newline
+                // doesn't leave a blank line behind
+                if self.column >= self.columns() {
+                    self.new_line();
+                }
+
+                let x = self.column as i32 * CHARACTER_SIZE;
+                let y = self.row as i32 * CHARACTER_SIZE;
+                let mut buffer = [0; 4];
+
+                let framebuffer = self.display.framebuffer_mut();
+                framebuffer.fill_rect(x, y, CHARACTER_SIZE, CHARACTER_SIZE, OLEDColour::BLACK);
+                framebuffer.text(character.encode_utf8(&mut buffer), x, y, OLEDColour::WHITE);
+
+                self.column += 1;
+            }
+        }
+    }
+}
+
+impl<'display, 'i2c, I: I2c> fmt::Write for Terminal<'display, 'i2c, I> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.toggle_cursor();
+
+        for character in text.chars() {
+            self.put_char(character);
+        }
+
+        self.toggle_cursor();
+
+        Ok(())
+    }
+}
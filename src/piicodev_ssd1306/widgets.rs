@@ -0,0 +1,274 @@
+//! Ready made ways of plotting sensor readings
+//!
+//! Each widget owns a rectangle of the display and redraws all of it every time, so it can be
+//! drawn over and over without clearing the display first.
+
+use core::fmt::{self, Write};
+
+use libm::{cosf, roundf, sinf};
+
+use super::drawing::CHARACTER_SIZE;
+use super::framebuffer::Framebuffer;
+use super::OLEDColour;
+use crate::ring_buffer::RingBuffer;
+
+/// Enough characters for any reading that fits next to a graph
+const LABEL_LENGTH: usize = 6;
+
+/// A number formatted on the stack, cut short if it doesn't fit
+struct Label {
+    buffer: [u8; LABEL_LENGTH],
+    len: usize,
+}
+
+impl Label {
+    fn new(value: f32, range: f32) -> Self {
+        let mut label = Self {
+            buffer: [0; LABEL_LENGTH],
+            len: 0,
+        };
+
+        // Writing into the buffer never fails, it just stops when full
+        let _ = if range < 10.0 {
+            write!(label, "{:.1}", value)
+        } else {
+            write!(label, "{:.0}", value)
+        };
+
+        label
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buffer[..self.len]).unwrap_or("")
+    }
+
+    fn width(&self) -> i32 {
+        self.len as i32 * CHARACTER_SIZE
+    }
+}
+
+impl Write for Label {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let length = text.len().min(LABEL_LENGTH - self.len);
+
+        self.buffer[self.len..self.len + length].copy_from_slice(&text.as_bytes()[..length]);
+        self.len += length;
+
+        Ok(())
+    }
+}
+
+///
+/// A line graph of the most recent readings, scrolling left as new ones arrive
+///
+/// The vertical axis scales itself to fit the readings on screen, with the highest and lowest
+/// values labelled down the left hand side. Missing readings (NaN) leave a gap.
+///
+pub struct Sparkline {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    /// Whether to label the top and bottom of the axis, which needs at least two lines of text
+    pub labels: bool,
+}
+
+impl Sparkline {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            labels: true,
+        }
+    }
+
+    pub fn draw<const N: usize>(
+        &self,
+        framebuffer: &mut Framebuffer,
+        readings: &RingBuffer<f32, N>,
+    ) {
+        framebuffer.fill_rect(self.x, self.y, self.width, self.height, OLEDColour::BLACK);
+
+        let (mut min, mut max) = readings
+            .iter()
+            .filter(|value| !value.is_nan())
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+
+        if min > max {
+            // Nothing to scale to yet
+            (min, max) = (0.0, 1.0);
+        } else if (max - min).abs() < f32::EPSILON {
+            // A flat line sits in the middle
+            (min, max) = (min - 0.5, max + 0.5);
+        }
+
+        let mut plot_x = self.x;
+
+        if self.labels && self.height >= 2 * CHARACTER_SIZE {
+            let top = Label::new(max, max - min);
+            let bottom = Label::new(min, max - min);
+            let bottom_y = self.y + self.height - CHARACTER_SIZE;
+
+            framebuffer.text(top.as_str(), self.x, self.y, OLEDColour::WHITE);
+            framebuffer.text(bottom.as_str(), self.x, bottom_y, OLEDColour::WHITE);
+
+            plot_x += top.width().max(bottom.width()) + 1;
+        }
+
+        let plot_width = self.x + self.width - plot_x;
+        let axis_y = self.y + self.height - 1;
+
+        if plot_width < 2 || self.height < 2 {
+            return;
+        }
+
+        framebuffer.vline(plot_x, self.y, self.height, OLEDColour::WHITE);
+        framebuffer.hline(plot_x, axis_y, plot_width, OLEDColour::WHITE);
+
+        // One reading per column, right of the axis and above it
+        let columns = (plot_width - 1) as usize;
+        let rows = self.height - 1;
+        let shown = readings.len().min(columns);
+        let first_x = plot_x + 1 + (columns - shown) as i32;
+
+        let to_y = |value: f32| {
+            let scaled = (max - value) / (max - min) * (rows - 1) as f32;
+            self.y + roundf(scaled) as i32
+        };
+
+        let mut previous: Option<(i32, i32)> = None;
+
+        for (i, value) in readings.iter().skip(readings.len() - shown).enumerate() {
+            if value.is_nan() {
+                previous = None;
+                continue;
+            }
+
+            let point = (first_x + i as i32, to_y(value));
+
+            match previous {
+                Some((x, y)) => framebuffer.line(x, y, point.0, point.1, OLEDColour::WHITE),
+                None => framebuffer.pixel(point.0, point.1, OLEDColour::WHITE),
+            }
+
+            previous = Some(point);
+        }
+    }
+}
+
+/// A horizontal bar filled in proportion to where a value sits between `min` and `max`
+pub struct BarGauge {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl BarGauge {
+    pub fn new(x: i32, y: i32, width: i32, height: i32, min: f32, max: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            min,
+            max,
+        }
+    }
+
+    /// Values outside of the range fill the bar or leave it empty, a missing one (NaN) empties it
+    pub fn draw(&self, framebuffer: &mut Framebuffer, value: f32) {
+        framebuffer.fill_rect(self.x, self.y, self.width, self.height, OLEDColour::BLACK);
+        framebuffer.rect(self.x, self.y, self.width, self.height, OLEDColour::WHITE);
+
+        // Leave a gap between the outline and the bar
+        let inner_width = self.width - 4;
+
+        if inner_width <= 0 || value.is_nan() || self.max <= self.min {
+            return;
+        }
+
+        let fraction = ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0);
+        let filled = roundf(fraction * inner_width as f32) as i32;
+
+        framebuffer.fill_rect(
+            self.x + 2,
+            self.y + 2,
+            filled,
+            self.height - 4,
+            OLEDColour::WHITE,
+        );
+    }
+}
+
+///
+/// A compass face with north at the top and a needle pointing at a heading
+///
+/// Headings are in degrees clockwise from north, as returned by the QMC6310.
+///
+pub struct CompassDial {
+    pub x: i32,
+    pub y: i32,
+    pub radius: i32,
+}
+
+impl CompassDial {
+    /// Centred on `x`, `y`
+    pub fn new(x: i32, y: i32, radius: i32) -> Self {
+        Self { x, y, radius }
+    }
+
+    /// Where a point `distance` from the centre at `heading` degrees lands
+    fn point(&self, heading: f32, distance: f32) -> (i32, i32) {
+        let radians = heading.to_radians();
+
+        (
+            self.x + roundf(distance * sinf(radians)) as i32,
+            self.y - roundf(distance * cosf(radians)) as i32,
+        )
+    }
+
+    /// A missing heading (NaN) draws the face without a needle
+    pub fn draw(&self, framebuffer: &mut Framebuffer, heading: f32) {
+        let r = self.radius;
+
+        framebuffer.fill_rect(
+            self.x - r,
+            self.y - r,
+            2 * r + 1,
+            2 * r + 1,
+            OLEDColour::BLACK,
+        );
+        framebuffer.circ(self.x, self.y, r + 1, 0.0, OLEDColour::WHITE);
+
+        // Longer ticks at the cardinal points
+        for tick in 0..8 {
+            let length = if tick % 2 == 0 { 3.0 } else { 1.0 };
+            let (x0, y0) = self.point(tick as f32 * 45.0, r as f32 - length);
+            let (x1, y1) = self.point(tick as f32 * 45.0, r as f32);
+
+            framebuffer.line(x0, y0, x1, y1, OLEDColour::WHITE);
+        }
+
+        // The font's glyphs are 5 pixels wide, starting at the left of their cell
+        if r >= 2 * CHARACTER_SIZE {
+            framebuffer.text("N", self.x - 2, self.y - r + 4, OLEDColour::WHITE);
+        }
+
+        if heading.is_nan() {
+            return;
+        }
+
+        let (tip_x, tip_y) = self.point(heading, r as f32 - 4.0);
+        let (tail_x, tail_y) = self.point(heading + 180.0, r as f32 / 3.0);
+
+        framebuffer.line(tail_x, tail_y, tip_x, tip_y, OLEDColour::WHITE);
+        framebuffer.circ(self.x, self.y, 2, 1.0, OLEDColour::WHITE);
+    }
+}
@@ -0,0 +1,80 @@
+///
+/// Fixed size history of readings, dropping the oldest once full
+///
+/// Kept on the stack, so the capacity is part of the type.
+///
+pub struct RingBuffer<T: Copy + Default, const N: usize> {
+    values: [T; N],
+    start: usize,
+    len: usize,
+}
+
+impl<T: Copy + Default, const N: usize> RingBuffer<T, N> {
+    pub fn new() -> Self {
+        Self {
+            values: [T::default(); N],
+            start: 0,
+            len: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+    }
+
+    /// Adds a reading, overwriting the oldest if there's no room left
+    pub fn push(&mut self, value: T) {
+        if N == 0 {
+            return;
+        }
+
+        if self.len < N {
+            self.values[(self.start + self.len) % N] = value;
+            self.len += 1;
+        } else {
+            self.values[self.start] = value;
+            self.start = (self.start + 1) % N;
+        }
+    }
+
+    /// The reading `index` places after the oldest
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        Some(self.values[(self.start + index) % N])
+    }
+
+    pub fn latest(&self) -> Option<T> {
+        self.len.checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// Readings from oldest to newest
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = T> + ExactSizeIterator + '_ {
+        (0..self.len).map(move |index| self.values[(self.start + index) % N])
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Default for RingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
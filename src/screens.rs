@@ -0,0 +1,69 @@
+//! The screens shown on the displays
+//!
+//! Kept apart from `main` so the host tests can draw exactly what the device does and compare it
+//! with the images in `host-tests/fixtures`.
+
+use core::fmt::{self, Write};
+
+use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyleBuilder};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyleBuilder, Rectangle};
+use embedded_graphics::text::{Alignment, Text};
+use embedded_hal::i2c::I2c;
+
+use crate::piicodev_ssd1306::{BarGauge, Framebuffer, Terminal};
+
+/// Readings further away than this fill the distance gauge, in millimetres
+const DISTANCE_RANGE: f32 = 2000.0;
+
+/// Black text on a white background, shown on startup
+pub fn splash<D: DrawTarget<Color = BinaryColor>>(display: &mut D) -> Result<(), D::Error> {
+    // NOTE: SSD1306 only supports binary colours: on and off (white and black)
+    let black_text_style = MonoTextStyleBuilder::new()
+        .font(&FONT_6X10)
+        .text_color(BinaryColor::Off)
+        .build();
+
+    let white_rectangle_style = PrimitiveStyleBuilder::new()
+        .fill_color(BinaryColor::On)
+        .stroke_color(BinaryColor::On)
+        .stroke_width(3)
+        .build();
+
+    Rectangle::new(Point::zero(), Size::new(128, 64))
+        .into_styled(white_rectangle_style)
+        .draw(display)?;
+
+    for (line, y) in [
+        ("80% of boys have", 8),
+        ("girlfriends", 24),
+        ("rest 20% are having", 40),
+        ("a brain", 56),
+    ] {
+        Text::with_alignment(line, Point::new(64, y), black_text_style, Alignment::Center)
+            .draw(display)?;
+    }
+
+    Ok(())
+}
+
+/// Logs a round of readings to the terminal, over two lines
+pub fn log_readings<I: I2c>(
+    terminal: &mut Terminal<'_, '_, I>,
+    temperature: f32,
+    pressure: f32,
+    humidity: f32,
+    altitude: f32,
+    distance: u16,
+) -> fmt::Result {
+    writeln!(terminal, "{:.1}C {:.0}hPa", temperature, pressure / 100.0)?;
+    writeln!(terminal, "{:.0}% {:.0}m {}mm", humidity, altitude, distance)
+}
+
+/// Fills a bar along the top of the dashboard with how far away something is
+pub fn distance(framebuffer: &mut Framebuffer, distance: u16) {
+    let gauge = BarGauge::new(0, 0, framebuffer.width() as i32, 12, 0.0, DISTANCE_RANGE);
+
+    gauge.draw(framebuffer, distance as f32);
+}